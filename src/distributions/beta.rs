@@ -1,4 +1,9 @@
-use crate::standard_distributions::{generate_random_state, standard_gamma};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::standard_gamma;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
 
 /// Beta Distribution
 /// # Example
@@ -13,24 +18,41 @@ use crate::standard_distributions::{generate_random_state, standard_gamma};
 /// // Change the parameters of the probability variable
 /// let shape_alpha: f64 = 2f64;
 /// let shape_beta: f64 = 1.5f64;
-/// let result: Result<(f64, f64), &str> = beta.try_set_params(shape_alpha, shape_beta);
+/// let result: Result<(f64, f64), rand_simple::ParameterUpdateError> = beta.try_set_params(shape_alpha, shape_beta);
 /// // Check the updated state
 /// assert_eq!(format!("{beta}"), "Beta(Shape parameter α, Shape parameter β) = Beta(2, 1.5)");
 /// // Generate another random number
 /// println!("Returns a random number -> {}", beta.sample());
+///
+/// // Fork the generator to replay a branch of the simulation
+/// let mut branch = beta.clone();
+/// for _ in 0..10 {
+///     assert_eq!(branch.sample(), beta.sample());
+/// }
 /// ```
-pub struct Beta {
-    xyzuv_u_alpha: [u32; 5],   // 状態変数
-    xyzuv_n_0_alpha: [u32; 5], // 状態変数
-    xyzuv_n_1_alpha: [u32; 5], // 状態変数
-    shape_alpha: f64,          // 形状母数 α
-
-    xyzuv_u_beta: [u32; 5],   // 状態変数
-    xyzuv_n_0_beta: [u32; 5], // 状態変数
-    xyzuv_n_1_beta: [u32; 5], // 状態変数
-    shape_beta: f64,          // 形状母数 β
+#[derive(Debug, Clone, PartialEq)]
+pub struct Beta<R = Xorshift160> {
+    rng_u_alpha: R,   // 状態変数
+    rng_n_0_alpha: R, // 状態変数
+    rng_n_1_alpha: R, // 状態変数
+    shape_alpha: f64, // 形状母数 α
+
+    rng_u_beta: R,   // 状態変数
+    rng_n_0_beta: R, // 状態変数
+    rng_n_1_beta: R, // 状態変数
+    shape_beta: f64, // 形状母数 β
+}
+
+/// Parameters of the [`Beta`] distribution, returned by [`Beta::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BetaParams {
+    /// Shape parameter α
+    pub shape_alpha: f64,
+    /// Shape parameter β
+    pub shape_beta: f64,
 }
 
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl Beta {
     // Constructor
     /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness.
@@ -38,34 +60,145 @@ impl Beta {
         // Adjust seeds to ensure uniqueness
         let adjusted_seeds = crate::adjust_seeds!(seeds);
 
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `shape_alpha` - Shape parameter α
+    /// * `shape_beta` - Shape parameter β
+    pub fn try_new(
+        seeds: [u32; 6_usize],
+        shape_alpha: f64,
+        shape_beta: f64,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(shape_alpha, shape_beta)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 6] {
+        [
+            self.rng_u_alpha.state(),
+            self.rng_n_0_alpha.state(),
+            self.rng_n_1_alpha.state(),
+            self.rng_u_beta.state(),
+            self.rng_n_0_beta.state(),
+            self.rng_n_1_beta.state(),
+        ]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 6],
+        params: BetaParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.shape_alpha, params.shape_beta)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Beta");
+        encoder.write(&params.shape_alpha);
+        encoder.write(&params.shape_beta);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Beta")?;
+        let params = BetaParams {
+            shape_alpha: decoder.read()?,
+            shape_beta: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Beta<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 6]) -> Self {
+        let [rng_u_alpha, rng_n_0_alpha, rng_n_1_alpha, rngs_beta @ ..] = rngs;
+        let [rng_u_beta, rng_n_0_beta, rng_n_1_beta] = rngs_beta;
         Self {
             // Alpha parameters
-            xyzuv_u_alpha: generate_random_state(adjusted_seeds[0]),
-            xyzuv_n_0_alpha: generate_random_state(adjusted_seeds[1]),
-            xyzuv_n_1_alpha: generate_random_state(adjusted_seeds[2]),
+            rng_u_alpha,
+            rng_n_0_alpha,
+            rng_n_1_alpha,
             shape_alpha: 1_f64,
 
             // Beta parameters
-            xyzuv_u_beta: generate_random_state(adjusted_seeds[3]),
-            xyzuv_n_0_beta: generate_random_state(adjusted_seeds[4]),
-            xyzuv_n_1_beta: generate_random_state(adjusted_seeds[5]),
+            rng_u_beta,
+            rng_n_0_beta,
+            rng_n_1_beta,
             shape_beta: 1_f64,
         }
     }
 
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
     // Beta Distribution Sampling
     /// Returns a random number following the Beta distribution
     pub fn sample(&mut self) -> f64 {
         let y1 = standard_gamma(
-            &mut self.xyzuv_u_alpha,
-            &mut self.xyzuv_n_0_alpha,
-            &mut self.xyzuv_n_1_alpha,
+            &mut self.rng_u_alpha,
+            &mut self.rng_n_0_alpha,
+            &mut self.rng_n_1_alpha,
             &self.shape_alpha,
         );
         let y2 = standard_gamma(
-            &mut self.xyzuv_u_beta,
-            &mut self.xyzuv_n_0_beta,
-            &mut self.xyzuv_n_1_beta,
+            &mut self.rng_u_beta,
+            &mut self.rng_n_0_beta,
+            &mut self.rng_n_1_beta,
             &self.shape_beta,
         );
         y1 / (y1 + y2)
@@ -79,21 +212,77 @@ impl Beta {
         &mut self,
         shape_alpha: f64,
         shape_beta: f64,
-    ) -> Result<(f64, f64), &str> {
-        if shape_alpha <= 0_f64 {
-            Err("Shape parameter α is less than or equal to 0. The parameters of the probability variable will remain unchanged from the previous settings.")
+    ) -> Result<(f64, f64), ParameterUpdateError> {
+        if !shape_alpha.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "shape_alpha",
+                value: shape_alpha,
+            })
+        } else if shape_alpha <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "shape_alpha",
+                value: shape_alpha,
+            })
         } else if shape_alpha == 1_f64 / 3_f64 {
-            Err("Shape parameter α is 1/3. The parameters of the probability variable will remain unchanged from the previous settings.")
+            Err(ParameterUpdateError::Unsupported {
+                name: "shape_alpha",
+                value: shape_alpha,
+            })
+        } else if !shape_beta.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "shape_beta",
+                value: shape_beta,
+            })
         } else if shape_beta <= 0_f64 {
-            Err("Shape parameter β is less than or equal to 0. The parameters of the probability variable will remain unchanged from the previous settings.")
+            Err(ParameterUpdateError::NonPositive {
+                name: "shape_beta",
+                value: shape_beta,
+            })
         } else if shape_beta == 1_f64 / 3_f64 {
-            Err("Shape parameter β is 1/3. The parameters of the probability variable will remain unchanged from the previous settings.")
+            Err(ParameterUpdateError::Unsupported {
+                name: "shape_beta",
+                value: shape_beta,
+            })
         } else {
             self.shape_alpha = shape_alpha;
             self.shape_beta = shape_beta;
             Ok((shape_alpha, shape_beta))
         }
     }
+
+    /// Returns the shape parameter α.
+    pub fn shape_alpha(&self) -> f64 {
+        self.shape_alpha
+    }
+
+    /// Returns the shape parameter β.
+    pub fn shape_beta(&self) -> f64 {
+        self.shape_beta
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> BetaParams {
+        BetaParams {
+            shape_alpha: self.shape_alpha(),
+            shape_beta: self.shape_beta(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for Beta<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [
+            &mut self.rng_u_alpha,
+            &mut self.rng_n_0_alpha,
+            &mut self.rng_n_1_alpha,
+            &mut self.rng_u_beta,
+            &mut self.rng_n_0_beta,
+            &mut self.rng_n_1_beta,
+        ] {
+            rng.jump_substream();
+        }
+    }
 }
 
 // Beta Distribution Display Formatter
@@ -101,7 +290,7 @@ impl Beta {
 /// * Struct type
 /// * Shape parameter α
 /// * Shape parameter β
-impl core::fmt::Display for Beta {
+impl<R: CoreRng> core::fmt::Display for Beta<R> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(
             f,
@@ -111,3 +300,12 @@ impl core::fmt::Display for Beta {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for Beta<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        Beta::sample(self)
+    }
+}
@@ -1,5 +1,8 @@
-use crate::standard_distributions::{
-    generate_random_state, standard_gamma, xorshift160_0_or_greater_and_less_than_1,
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::{standard_gamma, uniform_0_or_greater_and_less_than_1};
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
 };
 
 /// Chi Distribution
@@ -21,7 +24,7 @@ use crate::standard_distributions::{
 ///
 /// // Change the degrees of freedom
 /// let degree_of_freedom: u64 = 2_u64;
-/// let result: Result<u64, &str> = chi.try_set_params(degree_of_freedom);
+/// let result: Result<u64, rand_simple::ParameterUpdateError> = chi.try_set_params(degree_of_freedom);
 ///
 /// // Verify the new state
 /// assert_eq!(format!("{chi}"), "χ(Degree of Freedom parameter) = χ(2)");
@@ -32,67 +35,180 @@ use crate::standard_distributions::{
 ///
 /// # Fields
 ///
-/// * `xyzuv_u_gamma` - State variable for gamma distribution random number generation
-/// * `xyzuv_n_0_gamma` - State variable for gamma distribution random number generation
-/// * `xyzuv_n_1_gamma` - State variable for gamma distribution random number generation
-/// * `xyzuv_uniform` - State variable for uniform distribution random number generation
+/// * `rng_u_gamma` - State variable for gamma distribution random number generation
+/// * `rng_n_0_gamma` - State variable for gamma distribution random number generation
+/// * `rng_n_1_gamma` - State variable for gamma distribution random number generation
+/// * `rng_uniform` - State variable for uniform distribution random number generation
 /// * `degree_of_freedom` - Degrees of freedom, must be a positive integer
 ///
-pub struct Chi {
-    xyzuv_u_gamma: [u32; 5], // State variable for gamma distribution random number generation
-    xyzuv_n_0_gamma: [u32; 5], // State variable for gamma distribution random number generation
-    xyzuv_n_1_gamma: [u32; 5], // State variable for gamma distribution random number generation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chi<R = Xorshift160> {
+    rng_u_gamma: R,   // State variable for gamma distribution random number generation
+    rng_n_0_gamma: R, // State variable for gamma distribution random number generation
+    rng_n_1_gamma: R, // State variable for gamma distribution random number generation
 
-    xyzuv_uniform: [u32; 5], // State variable for uniform distribution random number generation
+    rng_uniform: R, // State variable for uniform distribution random number generation
 
     degree_of_freedom: u64, // Degrees of freedom, must be a positive integer
 }
 
+/// Parameters of the [`Chi`] distribution, returned by [`Chi::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiParams {
+    /// The degrees of freedom `r` (should be a natural number)
+    pub degree_of_freedom: u64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl Chi {
     /// Constructor for the `Chi` struct
     /// # Arguments
     ///
     /// * `seeds` - An array of 4 unsigned 32-bit integers used as seeds for the random number generators.
-    ///             The seeds are adjusted to ensure they are unique and suitable for the random number generation.
+    ///   The seeds are adjusted to ensure they are unique and suitable for the random number generation.
     pub fn new(seeds: [u32; 4_usize]) -> Self {
         // Adjust the seeds to ensure they are suitable for the random number generation
         let adjusted_seeds = crate::adjust_seeds!(seeds);
 
         // Create a new instance of the Chi struct with the adjusted seeds
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `degree_of_freedom` - The degrees of freedom `r` (should be a natural number)
+    pub fn try_new(
+        seeds: [u32; 4_usize],
+        degree_of_freedom: u64,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(degree_of_freedom)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 4] {
+        [
+            self.rng_u_gamma.state(),
+            self.rng_n_0_gamma.state(),
+            self.rng_n_1_gamma.state(),
+            self.rng_uniform.state(),
+        ]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 4],
+        params: ChiParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.degree_of_freedom)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Chi");
+        encoder.write(&params.degree_of_freedom);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Chi")?;
+        let params = ChiParams {
+            degree_of_freedom: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Chi<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 4]) -> Self {
+        let [rng_u_gamma, rng_n_0_gamma, rng_n_1_gamma, rng_uniform] = rngs;
         Self {
             // Initialize the state variables for gamma distribution random number generation
-            xyzuv_u_gamma: generate_random_state(adjusted_seeds[0]), // Seed for gamma distribution random number generator 1
-            xyzuv_n_0_gamma: generate_random_state(adjusted_seeds[1]), // Seed for gamma distribution random number generator 2
-            xyzuv_n_1_gamma: generate_random_state(adjusted_seeds[2]), // Seed for gamma distribution random number generator 3
+            rng_u_gamma,
+            rng_n_0_gamma,
+            rng_n_1_gamma,
 
             // Initialize the state variable for uniform distribution random number generation
-            xyzuv_uniform: generate_random_state(adjusted_seeds[3]), // Seed for uniform distribution random number generator
+            rng_uniform,
 
             // Set the initial degrees of freedom
             degree_of_freedom: 1_u64, // Default degrees of freedom
         }
     }
 
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
     /// Generates a random number following the Chi distribution.
     pub fn sample(&mut self) -> f64 {
         // Step 1: Generate a random number Y following the Chi-squared distribution (χ^2(x))
         let y = if self.degree_of_freedom > 1_u64 {
             // If degrees of freedom > 1, generate a gamma distribution random number with the degrees of freedom as the shape parameter
             standard_gamma(
-                &mut self.xyzuv_u_gamma,
-                &mut self.xyzuv_n_0_gamma,
-                &mut self.xyzuv_n_1_gamma,
+                &mut self.rng_u_gamma,
+                &mut self.rng_n_0_gamma,
+                &mut self.rng_n_1_gamma,
                 &(self.degree_of_freedom as f64),
             ) * 2_f64
         } else {
             // If degrees of freedom == 1, generate a random number using a specific method
             let y = standard_gamma(
-                &mut self.xyzuv_u_gamma,
-                &mut self.xyzuv_n_0_gamma,
-                &mut self.xyzuv_n_1_gamma,
+                &mut self.rng_u_gamma,
+                &mut self.rng_n_0_gamma,
+                &mut self.rng_n_1_gamma,
                 &(3_f64 / 2_f64),
             ) * 2_f64;
-            let u = xorshift160_0_or_greater_and_less_than_1(&mut self.xyzuv_uniform); // Generate a uniform random number in the interval (0, 1)
+            let u = uniform_0_or_greater_and_less_than_1(&mut self.rng_uniform); // Generate a uniform random number in the interval (0, 1)
             u.powi(2) * y * 2_f64
         };
         // Step 2: Calculate X = √Y
@@ -103,11 +219,12 @@ impl Chi {
     /// # Parameters
     ///
     /// * `degree_of_freedom` - The degrees of freedom `r` (should be a natural number).
-    pub fn try_set_params(&mut self, degree_of_freedom: u64) -> Result<u64, &str> {
-        // Step 1: Validate the degrees of freedom
+    pub fn try_set_params(&mut self, degree_of_freedom: u64) -> Result<u64, ParameterUpdateError> {
         if degree_of_freedom < 1_u64 {
-            // If the degrees of freedom is less than 1, return an error message
-            Err("自由度は自然数である必要があります。確率変数のパラメータは前回の設定を維持します。")
+            Err(ParameterUpdateError::NonPositive {
+                name: "degree_of_freedom",
+                value: degree_of_freedom as f64,
+            })
         } else {
             // Step 2: Update the internal state with the new degrees of freedom
             self.degree_of_freedom = degree_of_freedom;
@@ -115,9 +232,35 @@ impl Chi {
             Ok(degree_of_freedom)
         }
     }
+
+    /// Returns the degrees of freedom `r`.
+    pub fn degree_of_freedom(&self) -> u64 {
+        self.degree_of_freedom
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> ChiParams {
+        ChiParams {
+            degree_of_freedom: self.degree_of_freedom(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for Chi<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [
+            &mut self.rng_u_gamma,
+            &mut self.rng_n_0_gamma,
+            &mut self.rng_n_1_gamma,
+            &mut self.rng_uniform,
+        ] {
+            rng.jump_substream();
+        }
+    }
 }
 
-impl core::fmt::Display for Chi {
+impl<R: CoreRng> core::fmt::Display for Chi<R> {
     /// Formatter for displaying in macros like println!
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         // Writes the formatted string to the formatter
@@ -129,3 +272,12 @@ impl core::fmt::Display for Chi {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for Chi<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        Chi::sample(self)
+    }
+}
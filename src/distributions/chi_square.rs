@@ -1,6 +1,10 @@
+use crate::serialization::{Decoder, Encoder};
 use crate::standard_distributions::{
-    generate_random_state, standard_exponential, standard_gamma,
-    xorshift160_greater_than_0_and_less_than_1,
+    standard_exponential, standard_gamma, uniform_greater_than_0_and_less_than_1,
+};
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
 };
 
 /// Chi-Square Distribution
@@ -21,7 +25,7 @@ use crate::standard_distributions::{
 ///
 /// // Change the parameters of the random variable
 /// let degree_of_freedom: u64 = 2_u64;
-/// let result: Result<u64, &str> = chi_square.try_set_params(degree_of_freedom);
+/// let result: Result<u64, rand_simple::ParameterUpdateError> = chi_square.try_set_params(degree_of_freedom);
 ///
 /// // Output the updated string representation of the distribution
 /// assert_eq!(format!("{chi_square}"), "χ^2(Degree of Freedom parameter) = χ^2(2)");
@@ -29,17 +33,26 @@ use crate::standard_distributions::{
 /// // Generate a random number following the distribution with updated parameters
 /// println!("Generates a random number following Chi-Square distribution with {} degrees of freedom -> {}", degree_of_freedom, chi_square.sample());
 /// ```
-pub struct ChiSquare {
-    xyzuv_u_gamma: [u32; 5],   // State variables
-    xyzuv_n_0_gamma: [u32; 5], // State variables
-    xyzuv_n_1_gamma: [u32; 5], // State variables
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChiSquare<R = Xorshift160> {
+    rng_u_gamma: R,   // State variables
+    rng_n_0_gamma: R, // State variables
+    rng_n_1_gamma: R, // State variables
 
-    xyzuv_uniform: [u32; 5], // State variables
+    rng_uniform: R, // State variables
 
     degree_of_freedom: u64, // Degrees of freedom (𝑟)
     r_div2: f64,            // Preprocessing
 }
 
+/// Parameters of the [`ChiSquare`] distribution, returned by [`ChiSquare::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquareParams {
+    /// The degree of freedom (r) parameter of the distribution
+    pub degree_of_freedom: u64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl ChiSquare {
     /// Chi-Square Distribution Constructor
     ///
@@ -58,18 +71,122 @@ impl ChiSquare {
         // Adjusting seeds to ensure uniqueness
         let adjusted_seeds = crate::adjust_seeds!(seeds);
 
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `degree_of_freedom` - The degree of freedom (r) parameter of the distribution
+    pub fn try_new(
+        seeds: [u32; 4_usize],
+        degree_of_freedom: u64,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(degree_of_freedom)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 4] {
+        [
+            self.rng_u_gamma.state(),
+            self.rng_n_0_gamma.state(),
+            self.rng_n_1_gamma.state(),
+            self.rng_uniform.state(),
+        ]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 4],
+        params: ChiSquareParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.degree_of_freedom)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("ChiSquare");
+        encoder.write(&params.degree_of_freedom);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "ChiSquare")?;
+        let params = ChiSquareParams {
+            degree_of_freedom: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> ChiSquare<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 4]) -> Self {
+        let [rng_u_gamma, rng_n_0_gamma, rng_n_1_gamma, rng_uniform] = rngs;
         Self {
-            xyzuv_u_gamma: generate_random_state(adjusted_seeds[0]), // State variable for gamma distribution 1
-            xyzuv_n_0_gamma: generate_random_state(adjusted_seeds[1]), // State variable for gamma distribution 2
-            xyzuv_n_1_gamma: generate_random_state(adjusted_seeds[2]), // State variable for gamma distribution 3
+            rng_u_gamma,
+            rng_n_0_gamma,
+            rng_n_1_gamma,
 
-            xyzuv_uniform: generate_random_state(adjusted_seeds[3]), // State variable for uniform distribution
+            rng_uniform,
 
             degree_of_freedom: 1u64, // Degrees of freedom
             r_div2: 0.5f64,          // Preprocessing for random number calculation
         }
     }
 
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
     /// Generates a Random Number
     ///
     /// This method generates a random number based on the Chi-Square distribution algorithm.
@@ -86,13 +203,13 @@ impl ChiSquare {
     pub fn sample(&mut self) -> f64 {
         if self.degree_of_freedom == 2u64 {
             // When degree of freedom is 2, it is equivalent to Exponential distribution Exp(2)
-            2f64 * standard_exponential(&mut self.xyzuv_uniform)
+            2f64 * standard_exponential(&mut self.rng_uniform)
         } else if self.degree_of_freedom > 1u64 {
             // For r > 1, generates random number X following Gamma distribution Γ(r_div2, 2)
             standard_gamma(
-                &mut self.xyzuv_u_gamma,
-                &mut self.xyzuv_n_0_gamma,
-                &mut self.xyzuv_n_1_gamma,
+                &mut self.rng_u_gamma,
+                &mut self.rng_n_0_gamma,
+                &mut self.rng_n_1_gamma,
                 &self.r_div2,
             ) * 2f64
         } else {
@@ -100,12 +217,12 @@ impl ChiSquare {
             // Generates a uniform random number U in the interval (0, 1)
             // Calculates X = 2YU^2
             let y = standard_gamma(
-                &mut self.xyzuv_u_gamma,
-                &mut self.xyzuv_n_0_gamma,
-                &mut self.xyzuv_n_1_gamma,
+                &mut self.rng_u_gamma,
+                &mut self.rng_n_0_gamma,
+                &mut self.rng_n_1_gamma,
                 &(3f64 / 2f64),
             ) * 2f64;
-            let u = xorshift160_greater_than_0_and_less_than_1(&mut self.xyzuv_uniform);
+            let u = uniform_greater_than_0_and_less_than_1(&mut self.rng_uniform);
             u.powi(2) * y * 2_f64
         }
     }
@@ -122,10 +239,12 @@ impl ChiSquare {
     ///
     /// Returns `Ok(degree_of_freedom)` if successful, else returns an error message.
     ///
-    pub fn try_set_params(&mut self, degree_of_freedom: u64) -> Result<u64, &str> {
+    pub fn try_set_params(&mut self, degree_of_freedom: u64) -> Result<u64, ParameterUpdateError> {
         if degree_of_freedom < 1_u64 {
-            // Degree of freedom must be a natural number
-            Err("The degree of freedom must be a natural number. The parameters of the probability variable will remain unchanged.")
+            Err(ParameterUpdateError::NonPositive {
+                name: "degree_of_freedom",
+                value: degree_of_freedom as f64,
+            })
         } else {
             // Sets the degree of freedom and calculates r_div2
             self.degree_of_freedom = degree_of_freedom;
@@ -133,6 +252,32 @@ impl ChiSquare {
             Ok(degree_of_freedom)
         }
     }
+
+    /// Returns the degree of freedom (r).
+    pub fn degree_of_freedom(&self) -> u64 {
+        self.degree_of_freedom
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> ChiSquareParams {
+        ChiSquareParams {
+            degree_of_freedom: self.degree_of_freedom(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for ChiSquare<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [
+            &mut self.rng_u_gamma,
+            &mut self.rng_n_0_gamma,
+            &mut self.rng_n_1_gamma,
+            &mut self.rng_uniform,
+        ] {
+            rng.jump_substream();
+        }
+    }
 }
 
 /// Formatter for Displaying the Chi-Square Distribution
@@ -147,7 +292,7 @@ impl ChiSquare {
 ///
 /// Returns `core::fmt::Result`.
 ///
-impl core::fmt::Display for ChiSquare {
+impl<R: CoreRng> core::fmt::Display for ChiSquare<R> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         // Writes the formatted string to the formatter
         write!(
@@ -158,3 +303,12 @@ impl core::fmt::Display for ChiSquare {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for ChiSquare<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        ChiSquare::sample(self)
+    }
+}
@@ -0,0 +1,355 @@
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::standard_gamma_ln;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
+
+/// Dirichlet Distribution
+///
+/// A random vector on the probability simplex: its components are non-negative and sum to one.
+/// Each component is a gamma random number with shape parameter α_i divided by the sum of all of them.
+/// # Example
+/// ```
+/// let mut dirichlet = rand_simple::Dirichlet::new([1192_u32, 765_u32, 1543_u32]);
+/// assert_eq!(format!("{dirichlet}"), "Dir(Concentration parameters) = Dir(1, 1)");
+/// let weights: Vec<f64> = dirichlet.sample();
+/// assert!((weights.iter().sum::<f64>() - 1_f64).abs() < 1e-12_f64);
+///
+/// // Changing the parameters of the random variable
+/// let alpha: [f64; 3] = [0.5_f64, 1_f64, 2_f64];
+/// let result: Result<Vec<f64>, rand_simple::ParameterUpdateError> = dirichlet.try_set_params(&alpha);
+/// assert_eq!(format!("{dirichlet}"), "Dir(Concentration parameters) = Dir(0.5, 1, 2)");
+///
+/// // Fill a pre-allocated buffer instead of allocating a new vector
+/// let mut buffer = [0_f64; 3];
+/// dirichlet.sample_into(&mut buffer);
+/// println!("Mixture weights -> {buffer:?}");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dirichlet<R = Xorshift160> {
+    rng_u: R,        // 状態変数
+    rng_n_0: R,      // 状態変数
+    rng_n_1: R,      // 状態変数
+    alpha: Vec<f64>, // 集中度母数 α
+}
+
+/// Parameters of the [`Dirichlet`] distribution, returned by [`Dirichlet::params`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirichletParams {
+    /// Concentration parameters, one per component
+    pub alpha: Vec<f64>,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
+impl Dirichlet {
+    /// Constructs a new instance of the random number generator.
+    /// The concentration parameters are set to `[1, 1]`.
+    /// * `seeds` - Seeds for the random number generator. Adjusted internally to ensure uniqueness.
+    pub fn new(seeds: [u32; 3]) -> Self {
+        let adjusted_seeds = crate::adjust_seeds!(seeds);
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `alpha` - Concentration parameters, one per component
+    pub fn try_new(seeds: [u32; 3], alpha: &[f64]) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(alpha)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 3] {
+        [
+            self.rng_u.state(),
+            self.rng_n_0.state(),
+            self.rng_n_1.state(),
+        ]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 3],
+        params: DirichletParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(&params.alpha)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Dirichlet");
+        encoder.write(&params.alpha);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Dirichlet")?;
+        let params = DirichletParams {
+            alpha: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Dirichlet<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 3]) -> Self {
+        let [rng_u, rng_n_0, rng_n_1] = rngs;
+        Self {
+            rng_u,
+            rng_n_0,
+            rng_n_1,
+            alpha: vec![1_f64, 1_f64],
+        }
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
+    /// Generates a random vector whose components are non-negative and sum to one.
+    pub fn sample(&mut self) -> Vec<f64> {
+        let mut x: Vec<f64> = vec![0_f64; self.alpha.len()];
+        self.sample_into(&mut x);
+        x
+    }
+
+    /// Overwrites `dest` with a random vector whose components are non-negative and sum to one.
+    /// # Panics
+    /// Panics if the length of `dest` differs from [`Self::dimension`].
+    pub fn sample_into(&mut self, dest: &mut [f64]) {
+        assert_eq!(
+            dest.len(),
+            self.alpha.len(),
+            "the length of the buffer must equal the dimension of the Dirichlet distribution"
+        );
+        // step 1: 各成分について、形状母数 α_i のガンマ分布に従う乱数 y_i の対数 ln(y_i) を生成する
+        // α が非常に小さいと y_i そのものは 0 にアンダーフローし得るため、対数のまま扱う
+        let mut max: f64 = f64::NEG_INFINITY;
+        for (y, alpha) in dest.iter_mut().zip(self.alpha.iter()) {
+            *y = standard_gamma_ln(&mut self.rng_u, &mut self.rng_n_0, &mut self.rng_n_1, alpha);
+            max = max.max(*y);
+        }
+        // step 2: 最大値を引いてから指数をとり、和で割って正規化する (最大の成分は 1 になるため、和は 1 以上)
+        let mut sum: f64 = 0_f64;
+        for y in dest.iter_mut() {
+            *y = (*y - max).exp();
+            sum += *y;
+        }
+        for y in dest.iter_mut() {
+            *y /= sum;
+        }
+    }
+
+    /// Updates the parameters of the random variable.
+    /// * `alpha` - Concentration parameters, one per component. At least two components are required,
+    ///   and every component must be positive.
+    pub fn try_set_params(&mut self, alpha: &[f64]) -> Result<Vec<f64>, ParameterUpdateError> {
+        if alpha.len() < 2_usize {
+            return Err(ParameterUpdateError::OutOfRange {
+                name: "dimension",
+                value: alpha.len() as f64,
+                min: 2_f64,
+                max: f64::INFINITY,
+            });
+        }
+        for &alpha_i in alpha.iter() {
+            if !alpha_i.is_finite() {
+                return Err(ParameterUpdateError::NotFinite {
+                    name: "alpha",
+                    value: alpha_i,
+                });
+            } else if alpha_i <= 0_f64 {
+                return Err(ParameterUpdateError::NonPositive {
+                    name: "alpha",
+                    value: alpha_i,
+                });
+            }
+        }
+        self.alpha = alpha.to_vec();
+        Ok(self.alpha.clone())
+    }
+
+    /// Returns the concentration parameters.
+    pub fn alpha(&self) -> &[f64] {
+        &self.alpha
+    }
+
+    /// Returns the number of components of the random vector.
+    pub fn dimension(&self) -> usize {
+        self.alpha.len()
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> DirichletParams {
+        DirichletParams {
+            alpha: self.alpha.clone(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for Dirichlet<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [&mut self.rng_u, &mut self.rng_n_0, &mut self.rng_n_1] {
+            rng.jump_substream();
+        }
+    }
+}
+
+impl<R: CoreRng> core::fmt::Display for Dirichlet<R> {
+    /// Formatter for displaying in macros like print!
+    /// * Concentration parameters
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let alpha: Vec<String> = self.alpha.iter().map(|a| a.to_string()).collect();
+        write!(
+            f,
+            "Dir(Concentration parameters) = Dir({})",
+            alpha.join(", ")
+        )?;
+        Ok(())
+    }
+}
+
+impl<R: CoreRng> crate::Distribution for Dirichlet<R> {
+    type Output = Vec<f64>;
+
+    /// Generate a random vector. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> Vec<f64> {
+        Dirichlet::sample(self)
+    }
+}
+
+/// 各成分の平均が α_i / Σα と一致し、α が非常に小さくても生成が終了することを確認するテスト
+#[test]
+fn test_dirichlet_mean() {
+    for alpha in [
+        [0.5_f64, 1_f64, 2_f64],
+        [1_f64 / 3_f64, 1_f64, 3_f64],
+        [5_f64, 0.1_f64, 2.5_f64],
+    ] {
+        let mut dirichlet = Dirichlet::try_new([1192_u32, 765_u32, 1543_u32], &alpha).unwrap();
+        let mut mean = [0_f64; 3];
+        let mut x = [0_f64; 3];
+        for _ in 0..100_000 {
+            dirichlet.sample_into(&mut x);
+            assert!((x.iter().sum::<f64>() - 1_f64).abs() < 1e-12_f64);
+            for (mean_i, x_i) in mean.iter_mut().zip(x.iter()) {
+                *mean_i += x_i / 100_000_f64;
+            }
+        }
+        let sum: f64 = alpha.iter().sum();
+        for (mean_i, alpha_i) in mean.iter().zip(alpha.iter()) {
+            assert!((mean_i - alpha_i / sum).abs() < 0.01_f64);
+        }
+    }
+
+    // α が非常に小さい場合、ほぼ全ての確率が 1 つの成分に集中する
+    let mut dirichlet = Dirichlet::try_new([1192_u32, 765_u32, 1543_u32], &[1e-20_f64; 4]).unwrap();
+    for _ in 0..1_000 {
+        let x = dirichlet.sample();
+        assert!(x.iter().all(|x_i| x_i.is_finite() && *x_i >= 0_f64));
+        assert!((x.iter().sum::<f64>() - 1_f64).abs() < 1e-12_f64);
+        assert!(x.iter().any(|x_i| *x_i > 0.999_f64));
+    }
+}
+
+/// 不正な母数を設定した場合に、エラーを返して母数を変更しないことを確認するテスト
+#[test]
+fn test_dirichlet_invalid_params() {
+    let mut dirichlet = Dirichlet::new([1192_u32, 765_u32, 1543_u32]);
+    assert_eq!(
+        dirichlet.try_set_params(&[]),
+        Err(ParameterUpdateError::OutOfRange {
+            name: "dimension",
+            value: 0_f64,
+            min: 2_f64,
+            max: f64::INFINITY,
+        })
+    );
+    assert_eq!(
+        dirichlet.try_set_params(&[1_f64]),
+        Err(ParameterUpdateError::OutOfRange {
+            name: "dimension",
+            value: 1_f64,
+            min: 2_f64,
+            max: f64::INFINITY,
+        })
+    );
+    assert_eq!(
+        dirichlet.try_set_params(&[1_f64, 0_f64]),
+        Err(ParameterUpdateError::NonPositive {
+            name: "alpha",
+            value: 0_f64,
+        })
+    );
+    assert_eq!(
+        dirichlet.try_set_params(&[1_f64, -2_f64]),
+        Err(ParameterUpdateError::NonPositive {
+            name: "alpha",
+            value: -2_f64,
+        })
+    );
+    assert!(matches!(
+        dirichlet.try_set_params(&[f64::NAN, 1_f64]),
+        Err(ParameterUpdateError::NotFinite { name: "alpha", value }) if value.is_nan()
+    ));
+    assert!(matches!(
+        dirichlet.try_set_params(&[1_f64, f64::INFINITY]),
+        Err(ParameterUpdateError::NotFinite { name: "alpha", .. })
+    ));
+    assert_eq!(dirichlet.alpha(), &[1_f64, 1_f64]);
+    assert_eq!(
+        dirichlet.try_set_params(&[1_f64 / 3_f64, 1_f64]),
+        Ok(vec![1_f64 / 3_f64, 1_f64])
+    );
+}
@@ -1,4 +1,9 @@
-use crate::standard_distributions::{generate_random_state, standard_gamma};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::standard_gamma;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
 
 /// Erlang Distribution
 ///
@@ -19,7 +24,7 @@ use crate::standard_distributions::{generate_random_state, standard_gamma};
 /// // Modify the parameters of the random variable
 /// let shape: i64 = 2_i64;
 /// let scale: f64 = 1.5_f64;
-/// let result: Result<(i64, f64), &str> = erlang.try_set_params(shape, scale);
+/// let result: Result<(i64, f64), rand_simple::ParameterUpdateError> = erlang.try_set_params(shape, scale);
 ///
 /// // Ensure the parameters are updated correctly
 /// assert_eq!(format!("{erlang}"), "Er(Shape parameter, Scale parameter) = Er(2, 1.5)");
@@ -27,14 +32,25 @@ use crate::standard_distributions::{generate_random_state, standard_gamma};
 /// // Generate a random number from the modified Erlang distribution
 /// println!("Generating a random number following the Erlang distribution with shape parameter r = {} and scale parameter θ = {} -> {}", shape, scale, erlang.sample());
 /// ```
-pub struct Erlang {
-    xyzuv_u: [u32; 5],   // State variable
-    xyzuv_n_0: [u32; 5], // State variable
-    xyzuv_n_1: [u32; 5], // State variable
-    shape: f64,          // Shape parameter r ∈ N
-    scale: f64,          // Scale parameter
+#[derive(Debug, Clone, PartialEq)]
+pub struct Erlang<R = Xorshift160> {
+    rng_u: R,   // State variable
+    rng_n_0: R, // State variable
+    rng_n_1: R, // State variable
+    shape: f64, // Shape parameter r ∈ N
+    scale: f64, // Scale parameter
 }
 
+/// Parameters of the [`Erlang`] distribution, returned by [`Erlang::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErlangParams {
+    /// Shape parameter
+    pub shape: i64,
+    /// Scale parameter
+    pub scale: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl Erlang {
     /// Constructor
     ///
@@ -60,15 +76,122 @@ impl Erlang {
         let adjusted_seeds = crate::adjust_seeds!(seeds);
 
         // Create a new instance with adjusted seeds and default parameters
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `shape` - Shape parameter
+    /// * `scale` - Scale parameter
+    pub fn try_new(
+        seeds: [u32; 3_usize],
+        shape: i64,
+        scale: f64,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(shape, scale)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 3] {
+        [
+            self.rng_u.state(),
+            self.rng_n_0.state(),
+            self.rng_n_1.state(),
+        ]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 3],
+        params: ErlangParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.shape, params.scale)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Erlang");
+        encoder.write(&params.shape);
+        encoder.write(&params.scale);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Erlang")?;
+        let params = ErlangParams {
+            shape: decoder.read()?,
+            scale: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Erlang<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 3]) -> Self {
+        let [rng_u, rng_n_0, rng_n_1] = rngs;
         Self {
-            xyzuv_u: generate_random_state(adjusted_seeds[0]),
-            xyzuv_n_0: generate_random_state(adjusted_seeds[1]),
-            xyzuv_n_1: generate_random_state(adjusted_seeds[2]),
+            rng_u,
+            rng_n_0,
+            rng_n_1,
             shape: 1_f64, // Default shape parameter
             scale: 1_f64, // Default scale parameter
         }
     }
 
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
     /// Generate a random number.
     ///
     /// This method calculates a random number using the standard gamma distribution.
@@ -87,10 +210,10 @@ impl Erlang {
     pub fn sample(&mut self) -> f64 {
         // Call the standard_gamma function to generate a random number
         standard_gamma(
-            &mut self.xyzuv_u,   // Mutable reference to the state variable
-            &mut self.xyzuv_n_0, // Mutable reference to the state variable
-            &mut self.xyzuv_n_1, // Mutable reference to the state variable
-            &self.shape,         // Shape parameter of the gamma distribution
+            &mut self.rng_u,   // Mutable reference to the state variable
+            &mut self.rng_n_0, // Mutable reference to the state variable
+            &mut self.rng_n_1, // Mutable reference to the state variable
+            &self.shape,       // Shape parameter of the gamma distribution
         ) * self.scale // Multiply the generated random number by the scale parameter
     }
 
@@ -109,32 +232,77 @@ impl Erlang {
     ///
     /// # Errors
     ///
-    /// Returns an error message if the provided parameters are invalid.
+    /// Returns a `ParameterUpdateError` if the provided parameters are invalid.
     ///
     /// # Examples
     ///
     /// ```
     /// let mut erlang = rand_simple::Erlang::new([1192u32, 765u32, 1543u32]);
     /// assert_eq!(erlang.try_set_params(3, 0.5), Ok((3, 0.5)));
-    /// assert_eq!(erlang.try_set_params(0, 0.5), Err("The shape parameter is less than or equal to 0. The parameters of the random variable will remain unchanged."));
-    /// assert_eq!(erlang.try_set_params(1, -0.1), Err("The scale parameter is less than or equal to 0. The parameters of the random variable will remain unchanged."));
+    /// assert_eq!(erlang.try_set_params(0, 0.5), Err(rand_simple::ParameterUpdateError::NonPositive { name: "shape", value: 0.0 }));
+    /// assert_eq!(erlang.try_set_params(1, -0.1), Err(rand_simple::ParameterUpdateError::NonPositive { name: "scale", value: -0.1 }));
     /// ```
-    pub fn try_set_params(&mut self, shape: i64, scale: f64) -> Result<(i64, f64), &str> {
+    pub fn try_set_params(
+        &mut self,
+        shape: i64,
+        scale: f64,
+    ) -> Result<(i64, f64), ParameterUpdateError> {
         if shape <= 0_i64 {
-            Err("The shape parameter is less than or equal to 0. The parameters of the random variable will remain unchanged.")
+            Err(ParameterUpdateError::NonPositive {
+                name: "shape",
+                value: shape as f64,
+            })
         } else if shape as f64 == 1_f64 / 3_f64 {
-            Err("The shape parameter is equal to 1/3. The parameters of the random variable will remain unchanged.")
+            Err(ParameterUpdateError::Unsupported {
+                name: "shape",
+                value: shape as f64,
+            })
+        } else if !scale.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "scale",
+                value: scale,
+            })
         } else if scale <= 0_f64 {
-            Err("The scale parameter is less than or equal to 0. The parameters of the random variable will remain unchanged.")
+            Err(ParameterUpdateError::NonPositive {
+                name: "scale",
+                value: scale,
+            })
         } else {
             self.shape = shape as f64;
             self.scale = scale;
             Ok((shape, scale))
         }
     }
+
+    /// Returns the shape parameter.
+    pub fn shape(&self) -> i64 {
+        self.shape as i64
+    }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> ErlangParams {
+        ErlangParams {
+            shape: self.shape(),
+            scale: self.scale(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for Erlang<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [&mut self.rng_u, &mut self.rng_n_0, &mut self.rng_n_1] {
+            rng.jump_substream();
+        }
+    }
 }
 
-impl core::fmt::Display for Erlang {
+impl<R: CoreRng> core::fmt::Display for Erlang<R> {
     /// Formatter for displaying in macros like print!
     /// * Shape parameter
     /// * Scale parameter
@@ -147,3 +315,12 @@ impl core::fmt::Display for Erlang {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for Erlang<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        Erlang::sample(self)
+    }
+}
@@ -1,5 +1,8 @@
-use crate::standard_distributions::{
-    generate_random_state, standard_gamma, xorshift160_0_or_greater_and_less_than_1,
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::{standard_gamma, uniform_0_or_greater_and_less_than_1};
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
 };
 
 /// F-distribution
@@ -24,7 +27,7 @@ use crate::standard_distributions::{
 ///
 /// // Attempt to update the F-distribution's parameters using the new degrees of freedom.
 /// // The `try_set_params` method returns a `Result`, allowing us to handle any potential errors during this update.
-/// let result: Result<(u64, u64), &str> = f.try_set_params(degree_of_freedom_1, degree_of_freedom_2);
+/// let result: Result<(u64, u64), rand_simple::ParameterUpdateError> = f.try_set_params(degree_of_freedom_1, degree_of_freedom_2);
 ///
 /// // Verify that the degrees of freedom have been successfully updated to (2, 3).
 /// // This assertion ensures that the distribution is now set up with the new parameters.
@@ -34,25 +37,35 @@ use crate::standard_distributions::{
 /// // The `sample()` method now reflects the updated distribution parameters.
 /// println!("Generates a random number with the updated degrees of freedom ({}, {}) -> {}", degree_of_freedom_1, degree_of_freedom_2, f.sample());
 /// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FDistribution<R = Xorshift160> {
+    rng_u_gamma_1: R,   // 状態変数
+    rng_n_0_gamma_1: R, // 状態変数
+    rng_n_1_gamma_1: R, // 状態変数
 
-pub struct FDistribution {
-    xyzuv_u_gamma_1: [u32; 5],   // 状態変数
-    xyzuv_n_0_gamma_1: [u32; 5], // 状態変数
-    xyzuv_n_1_gamma_1: [u32; 5], // 状態変数
-
-    xyzuv_uniform_1: [u32; 5], // 状態変数
+    rng_uniform_1: R, // 状態変数
 
     degree_of_freedom_1: u64, // 自由度 r ∈ N
 
-    xyzuv_u_gamma_2: [u32; 5],   // 状態変数
-    xyzuv_n_0_gamma_2: [u32; 5], // 状態変数
-    xyzuv_n_1_gamma_2: [u32; 5], // 状態変数
+    rng_u_gamma_2: R,   // 状態変数
+    rng_n_0_gamma_2: R, // 状態変数
+    rng_n_1_gamma_2: R, // 状態変数
 
-    xyzuv_uniform_2: [u32; 5], // 状態変数
+    rng_uniform_2: R, // 状態変数
 
     degree_of_freedom_2: u64, // 自由度 r ∈ N
 }
 
+/// Parameters of the [`FDistribution`] distribution, returned by [`FDistribution::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FDistributionParams {
+    /// The first degree of freedom, associated with the numerator in the F-distribution
+    pub degree_of_freedom_1: u64,
+    /// The second degree of freedom, associated with the denominator in the F-distribution
+    pub degree_of_freedom_2: u64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl FDistribution {
     /// Constructor for the `FDistribution` struct.
     /// This function initializes a new instance of the `FDistribution` with given seeds.
@@ -68,25 +81,152 @@ impl FDistribution {
     pub fn new(seeds: [u32; 8_usize]) -> Self {
         let adjusted_seeds = crate::adjust_seeds!(seeds);
 
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `degree_of_freedom_1` - The first degree of freedom, associated with the numerator in the F-distribution
+    /// * `degree_of_freedom_2` - The second degree of freedom, associated with the denominator in the F-distribution
+    pub fn try_new(
+        seeds: [u32; 8_usize],
+        degree_of_freedom_1: u64,
+        degree_of_freedom_2: u64,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(degree_of_freedom_1, degree_of_freedom_2)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 8] {
+        [
+            self.rng_u_gamma_1.state(),
+            self.rng_n_0_gamma_1.state(),
+            self.rng_n_1_gamma_1.state(),
+            self.rng_uniform_1.state(),
+            self.rng_u_gamma_2.state(),
+            self.rng_n_0_gamma_2.state(),
+            self.rng_n_1_gamma_2.state(),
+            self.rng_uniform_2.state(),
+        ]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    /// # Example
+    /// ```
+    /// let mut f = rand_simple::FDistribution::try_new([1192_u32, 765_u32, 1543_u32, 2003_u32, 1867_u32, 1688_u32, 3107_u32, 4273_u32], 2_u64, 3_u64).unwrap();
+    /// let _ = f.sample();
+    ///
+    /// // Checkpoint
+    /// let (state, params) = (f.state(), f.params());
+    ///
+    /// // Resume
+    /// let mut resumed = rand_simple::FDistribution::from_state(state, params).unwrap();
+    /// for _ in 0..10 {
+    ///     assert_eq!(resumed.sample(), f.sample());
+    /// }
+    /// ```
+    pub fn from_state(
+        state: [[u32; 5]; 8],
+        params: FDistributionParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.degree_of_freedom_1, params.degree_of_freedom_2)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("FDistribution");
+        encoder.write(&params.degree_of_freedom_1);
+        encoder.write(&params.degree_of_freedom_2);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "FDistribution")?;
+        let params = FDistributionParams {
+            degree_of_freedom_1: decoder.read()?,
+            degree_of_freedom_2: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> FDistribution<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 8]) -> Self {
+        let [rng_u_gamma_1, rng_n_0_gamma_1, rng_n_1_gamma_1, rng_uniform_1, rngs_2 @ ..] = rngs;
+        let [rng_u_gamma_2, rng_n_0_gamma_2, rng_n_1_gamma_2, rng_uniform_2] = rngs_2;
         Self {
-            xyzuv_u_gamma_1: generate_random_state(adjusted_seeds[0]),
-            xyzuv_n_0_gamma_1: generate_random_state(adjusted_seeds[1]),
-            xyzuv_n_1_gamma_1: generate_random_state(adjusted_seeds[2]),
+            rng_u_gamma_1,
+            rng_n_0_gamma_1,
+            rng_n_1_gamma_1,
 
-            xyzuv_uniform_1: generate_random_state(adjusted_seeds[3]),
+            rng_uniform_1,
 
             degree_of_freedom_1: 1_u64,
 
-            xyzuv_u_gamma_2: generate_random_state(adjusted_seeds[4]),
-            xyzuv_n_0_gamma_2: generate_random_state(adjusted_seeds[5]),
-            xyzuv_n_1_gamma_2: generate_random_state(adjusted_seeds[6]),
+            rng_u_gamma_2,
+            rng_n_0_gamma_2,
+            rng_n_1_gamma_2,
 
-            xyzuv_uniform_2: generate_random_state(adjusted_seeds[7]),
+            rng_uniform_2,
 
             degree_of_freedom_2: 1_u64,
         }
     }
 
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
     /// Generates a random number that follows the F-distribution.
     ///
     /// This method implements the algorithm to compute a random variable that follows the F-distribution
@@ -100,37 +240,37 @@ impl FDistribution {
         // step 1: χ二乗分布の確率変数を2個生成する
         let chi_1 = if self.degree_of_freedom_1 > 1_u64 {
             standard_gamma(
-                &mut self.xyzuv_u_gamma_1,
-                &mut self.xyzuv_n_0_gamma_1,
-                &mut self.xyzuv_n_1_gamma_1,
+                &mut self.rng_u_gamma_1,
+                &mut self.rng_n_0_gamma_1,
+                &mut self.rng_n_1_gamma_1,
                 &(self.degree_of_freedom_1 as f64),
             ) * 2_f64
         } else {
             let y = standard_gamma(
-                &mut self.xyzuv_u_gamma_1,
-                &mut self.xyzuv_n_0_gamma_1,
-                &mut self.xyzuv_n_1_gamma_1,
+                &mut self.rng_u_gamma_1,
+                &mut self.rng_n_0_gamma_1,
+                &mut self.rng_n_1_gamma_1,
                 &(3_f64 / 2_f64),
             ) * 2_f64;
-            let u = xorshift160_0_or_greater_and_less_than_1(&mut self.xyzuv_uniform_1);
+            let u = uniform_0_or_greater_and_less_than_1(&mut self.rng_uniform_1);
             u.powi(2) * y * 2_f64
         };
 
         let chi_2 = if self.degree_of_freedom_2 > 1_u64 {
             standard_gamma(
-                &mut self.xyzuv_u_gamma_2,
-                &mut self.xyzuv_n_0_gamma_2,
-                &mut self.xyzuv_n_1_gamma_2,
+                &mut self.rng_u_gamma_2,
+                &mut self.rng_n_0_gamma_2,
+                &mut self.rng_n_1_gamma_2,
                 &(self.degree_of_freedom_2 as f64),
             ) * 2_f64
         } else {
             let y = standard_gamma(
-                &mut self.xyzuv_u_gamma_2,
-                &mut self.xyzuv_n_0_gamma_2,
-                &mut self.xyzuv_n_1_gamma_2,
+                &mut self.rng_u_gamma_2,
+                &mut self.rng_n_0_gamma_2,
+                &mut self.rng_n_1_gamma_2,
                 &(3_f64 / 2_f64),
             ) * 2_f64;
-            let u = xorshift160_0_or_greater_and_less_than_1(&mut self.xyzuv_uniform_2);
+            let u = uniform_0_or_greater_and_less_than_1(&mut self.rng_uniform_2);
             u.powi(2) * y * 2_f64
         };
 
@@ -152,16 +292,22 @@ impl FDistribution {
     /// A `Result` type:
     ///
     /// * `Ok((u64, u64))` - Returns a tuple containing the updated degrees of freedom if the parameters are valid.
-    /// * `Err(&str)` - Returns an error message if either degree of freedom is invalid (i.e., less than 1).
+    /// * `Err(ParameterUpdateError)` - Returns an error if either degree of freedom is invalid (i.e., less than 1).
     pub fn try_set_params(
         &mut self,
         degree_of_freedom_1: u64,
         degree_of_freedom_2: u64,
-    ) -> Result<(u64, u64), &str> {
-        // Validate that both degrees of freedom are natural numbers (greater than or equal to 1).
-        if degree_of_freedom_1 < 1_u64 || degree_of_freedom_2 < 1_u64 {
-            // If either degree of freedom is invalid, return an error message.
-            Err("Degrees of freedom must be natural numbers. The parameters will remain unchanged.")
+    ) -> Result<(u64, u64), ParameterUpdateError> {
+        if degree_of_freedom_1 < 1_u64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "degree_of_freedom_1",
+                value: degree_of_freedom_1 as f64,
+            })
+        } else if degree_of_freedom_2 < 1_u64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "degree_of_freedom_2",
+                value: degree_of_freedom_2 as f64,
+            })
         } else {
             // If the degrees of freedom are valid, update the struct's internal state.
             self.degree_of_freedom_1 = degree_of_freedom_1;
@@ -170,12 +316,48 @@ impl FDistribution {
             Ok((degree_of_freedom_1, degree_of_freedom_2))
         }
     }
+
+    /// Returns the first degree of freedom (numerator).
+    pub fn degree_of_freedom_1(&self) -> u64 {
+        self.degree_of_freedom_1
+    }
+
+    /// Returns the second degree of freedom (denominator).
+    pub fn degree_of_freedom_2(&self) -> u64 {
+        self.degree_of_freedom_2
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> FDistributionParams {
+        FDistributionParams {
+            degree_of_freedom_1: self.degree_of_freedom_1(),
+            degree_of_freedom_2: self.degree_of_freedom_2(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for FDistribution<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [
+            &mut self.rng_u_gamma_1,
+            &mut self.rng_n_0_gamma_1,
+            &mut self.rng_n_1_gamma_1,
+            &mut self.rng_uniform_1,
+            &mut self.rng_u_gamma_2,
+            &mut self.rng_n_0_gamma_2,
+            &mut self.rng_n_1_gamma_2,
+            &mut self.rng_uniform_2,
+        ] {
+            rng.jump_substream();
+        }
+    }
 }
 
 /// Implementing the `Display` trait for the `FDistribution` struct
 /// This allows instances of `FDistribution` to be formatted as a string,
 /// making it easier to print and debug the object.
-impl core::fmt::Display for FDistribution {
+impl<R: CoreRng> core::fmt::Display for FDistribution<R> {
     /// Formats the `FDistribution` instance for display purposes.
     ///
     /// # Arguments
@@ -198,3 +380,12 @@ impl core::fmt::Display for FDistribution {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for FDistribution<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        FDistribution::sample(self)
+    }
+}
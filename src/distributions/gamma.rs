@@ -1,4 +1,9 @@
-use crate::standard_distributions::{generate_random_state, standard_gamma};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::standard_gamma;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
 
 /// Gamma Distribution
 /// # Example
@@ -10,18 +15,29 @@ use crate::standard_distributions::{generate_random_state, standard_gamma};
 /// // Changing the parameters of the random variable
 /// let shape: f64 = 2f64;
 /// let scale: f64 = 1.5f64;
-/// let result: Result<(f64, f64), &str> = gamma.try_set_params(shape, scale);
+/// let result: Result<(f64, f64), rand_simple::ParameterUpdateError> = gamma.try_set_params(shape, scale);
 /// assert_eq!(format!("{gamma}"), "Γ(Shape parameter, Scale parameter) = Γ(2, 1.5)");
 /// println!("Generating a random number following the gamma distribution with shape parameter α = {}, and scale parameter β = {} -> {}", shape, scale, gamma.sample());
 /// ```
-pub struct Gamma {
-    xyzuv_u: [u32; 5],   // State variables
-    xyzuv_n_0: [u32; 5], // State variables
-    xyzuv_n_1: [u32; 5], // State variables
-    shape: f64,          // Shape parameter α
-    scale: f64,          // Scale parameter β
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gamma<R = Xorshift160> {
+    rng_u: R,   // State variables
+    rng_n_0: R, // State variables
+    rng_n_1: R, // State variables
+    shape: f64, // Shape parameter α
+    scale: f64, // Scale parameter β
 }
 
+/// Parameters of the [`Gamma`] distribution, returned by [`Gamma::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GammaParams {
+    /// Shape parameter
+    pub shape: f64,
+    /// Scale parameter
+    pub scale: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl Gamma {
     // Constructor
     /// Constructs a new instance of the random number generator.
@@ -30,23 +46,126 @@ impl Gamma {
         // Adjust the seeds to ensure uniqueness
         let adjusted_seeds = crate::adjust_seeds!(seeds);
         // Create a new instance of the random number generator
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `shape` - Shape parameter
+    /// * `scale` - Scale parameter
+    pub fn try_new(seeds: [u32; 3], shape: f64, scale: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(shape, scale)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 3] {
+        [
+            self.rng_u.state(),
+            self.rng_n_0.state(),
+            self.rng_n_1.state(),
+        ]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 3],
+        params: GammaParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.shape, params.scale)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Gamma");
+        encoder.write(&params.shape);
+        encoder.write(&params.scale);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Gamma")?;
+        let params = GammaParams {
+            shape: decoder.read()?,
+            scale: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Gamma<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 3]) -> Self {
+        let [rng_u, rng_n_0, rng_n_1] = rngs;
         Self {
-            xyzuv_u: generate_random_state(adjusted_seeds[0]),
-            xyzuv_n_0: generate_random_state(adjusted_seeds[1]),
-            xyzuv_n_1: generate_random_state(adjusted_seeds[2]),
+            rng_u,
+            rng_n_0,
+            rng_n_1,
             shape: 1_f64,
             scale: 1_f64,
         }
     }
 
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
     // Function to compute random numbers
     /// It computes a random number using the standard gamma distribution.
     pub fn sample(&mut self) -> f64 {
         // Generate a random number using the standard gamma distribution
         let random_number = standard_gamma(
-            &mut self.xyzuv_u,
-            &mut self.xyzuv_n_0,
-            &mut self.xyzuv_n_1,
+            &mut self.rng_u,
+            &mut self.rng_n_0,
+            &mut self.rng_n_1,
             &self.shape,
         );
         // Scale the random number by the scale parameter and return it
@@ -56,21 +175,36 @@ impl Gamma {
     // Function to change the parameters of the probability variable
     /// * `shape` - Shape parameter
     /// * `scale` - Scale parameter
-    pub fn try_set_params(&mut self, shape: f64, scale: f64) -> Result<(f64, f64), &str> {
-        // Check if the shape parameter is less than or equal to 0
-        if shape <= 0_f64 {
-            // Return an error if the shape parameter is less than or equal to 0
-            Err("The shape parameter is less than or equal to 0. The parameters of the probability variable will remain unchanged.")
-        }
-        // Check if the shape parameter is equal to 1/3
-        else if shape == 1_f64 / 3_f64 {
-            // Return an error if the shape parameter is equal to 1/3
-            Err("The shape parameter is 1/3. The parameters of the probability variable will remain unchanged.")
-        }
-        // Check if the scale parameter is less than or equal to 0
-        else if scale <= 0_f64 {
-            // Return an error if the scale parameter is less than or equal to 0
-            Err("The scale parameter is less than or equal to 0. The parameters of the probability variable will remain unchanged.")
+    pub fn try_set_params(
+        &mut self,
+        shape: f64,
+        scale: f64,
+    ) -> Result<(f64, f64), ParameterUpdateError> {
+        if !shape.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "shape",
+                value: shape,
+            })
+        } else if shape <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "shape",
+                value: shape,
+            })
+        } else if shape == 1_f64 / 3_f64 {
+            Err(ParameterUpdateError::Unsupported {
+                name: "shape",
+                value: shape,
+            })
+        } else if !scale.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "scale",
+                value: scale,
+            })
+        } else if scale <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "scale",
+                value: scale,
+            })
         } else {
             // Set the new shape and scale parameters
             self.shape = shape;
@@ -79,9 +213,36 @@ impl Gamma {
             Ok((shape, scale))
         }
     }
+
+    /// Returns the shape parameter.
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> GammaParams {
+        GammaParams {
+            shape: self.shape(),
+            scale: self.scale(),
+        }
+    }
 }
 
-impl core::fmt::Display for Gamma {
+impl<R: SplittableRng> SplittableDistribution for Gamma<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [&mut self.rng_u, &mut self.rng_n_0, &mut self.rng_n_1] {
+            rng.jump_substream();
+        }
+    }
+}
+
+impl<R: CoreRng> core::fmt::Display for Gamma<R> {
     /// Formatter for displaying in macros like print!
     /// * Shape parameter
     /// * Scale parameter
@@ -94,3 +255,12 @@ impl core::fmt::Display for Gamma {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for Gamma<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        Gamma::sample(self)
+    }
+}
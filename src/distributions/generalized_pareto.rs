@@ -0,0 +1,305 @@
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::uniform_greater_than_0_and_less_than_1;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, RandomAccessRng, SeedSequence,
+    SplittableDistribution, SplittableRng, Xorshift160,
+};
+
+/// Generalized Pareto Distribution
+///
+/// The distribution of exceedances over a high threshold in peaks-over-threshold analysis,
+/// with location μ, scale σ and shape ξ. The survival function is `(1 + ξ (x - μ) / σ)^(-1/ξ)`,
+/// which becomes the exponential distribution for ξ = 0, the [`crate::Lomax`] distribution for ξ > 0,
+/// and a distribution bounded above by `μ - σ / ξ` for ξ < 0. It is sampled by inversion.
+/// # Example
+/// ```
+/// let mut generalized_pareto = rand_simple::GeneralizedPareto::new(1192_u32);
+/// assert_eq!(format!("{generalized_pareto}"), "GPD(Location parameter, Scale parameter, Shape parameter) = GPD(0, 1, 0)");
+/// println!("Returns a random number -> {}", generalized_pareto.sample());
+///
+/// // If you want to change the parameters of the random variable
+/// let location: f64 = 10_f64;
+/// let scale: f64 = 2_f64;
+/// let shape: f64 = 0.25_f64;
+/// let result: Result<(f64, f64, f64), rand_simple::ParameterUpdateError> = generalized_pareto.try_set_params(location, scale, shape);
+/// assert_eq!(format!("{generalized_pareto}"), "GPD(Location parameter, Scale parameter, Shape parameter) = GPD(10, 2, 0.25)");
+/// println!("Returns a random number -> {}", generalized_pareto.sample());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneralizedPareto<R = Xorshift160> {
+    rng: R,        // 状態変数
+    location: f64, // 位置母数
+    scale: f64,    // 尺度母数
+    shape: f64,    // 形状母数
+}
+
+/// Parameters of the [`GeneralizedPareto`] distribution, returned by [`GeneralizedPareto::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneralizedParetoParams {
+    /// Location parameter
+    pub location: f64,
+    /// Scale parameter
+    pub scale: f64,
+    /// Shape parameter
+    pub shape: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
+impl GeneralizedPareto {
+    /// Constructor
+    /// * `_seed` - Random number seed
+    pub fn new(_seed: u32) -> Self {
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `location` - Location parameter
+    /// * `scale` - Scale parameter
+    /// * `shape` - Shape parameter
+    pub fn try_new(
+        seed: u32,
+        location: f64,
+        scale: f64,
+        shape: f64,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(location, scale, shape)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [u32; 5],
+        params: GeneralizedParetoParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.location, params.scale, params.shape)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("GeneralizedPareto");
+        encoder.write(&params.location);
+        encoder.write(&params.scale);
+        encoder.write(&params.shape);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "GeneralizedPareto")?;
+        let params = GeneralizedParetoParams {
+            location: decoder.read()?,
+            scale: decoder.read()?,
+            shape: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> GeneralizedPareto<R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
+        Self {
+            rng,
+            location: 0_f64,
+            scale: 1_f64,
+            shape: 0_f64,
+        }
+    }
+
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
+    }
+
+    /// Calculate random numbers.
+    pub fn sample(&mut self) -> f64 {
+        // 逆関数法: 開区間 (0, 1) の一様乱数 u から μ + σ (u^(-ξ) - 1) / ξ を計算する
+        let u: f64 = uniform_greater_than_0_and_less_than_1(&mut self.rng);
+        let y: f64 = if self.shape == 0_f64 {
+            // ξ = 0 のときは指数分布になる
+            -u.ln()
+        } else {
+            // ξ が 0 に近いときの桁落ちを避けるため、exp_m1 を使用する
+            (-self.shape * u.ln()).exp_m1() / self.shape
+        };
+        self.location + self.scale * y
+    }
+
+    /// Modify the parameters of the random variable.
+    /// * `location` - Location parameter
+    /// * `scale` - Scale parameter
+    /// * `shape` - Shape parameter
+    pub fn try_set_params(
+        &mut self,
+        location: f64,
+        scale: f64,
+        shape: f64,
+    ) -> Result<(f64, f64, f64), ParameterUpdateError> {
+        if !location.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "location",
+                value: location,
+            })
+        } else if !scale.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "scale",
+                value: scale,
+            })
+        } else if scale <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "scale",
+                value: scale,
+            })
+        } else if !shape.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "shape",
+                value: shape,
+            })
+        } else {
+            self.location = location;
+            self.scale = scale;
+            self.shape = shape;
+            Ok((location, scale, shape))
+        }
+    }
+
+    /// Returns the location parameter.
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the shape parameter.
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> GeneralizedParetoParams {
+        GeneralizedParetoParams {
+            location: self.location(),
+            scale: self.scale(),
+            shape: self.shape(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for GeneralizedPareto<R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
+}
+
+impl<R: RandomAccessRng> GeneralizedPareto<R> {
+    /// Returns the random number at `index` of the stream without generating the preceding ones,
+    /// leaving the state of `self` unchanged.
+    /// It equals the result of the `index`-th call (counting from 0) of `sample` from the start of the stream,
+    /// unless an earlier call rejected a uniform random number, which happens with probability about 2^-32 per call.
+    pub fn sample_at(&self, index: u128) -> f64 {
+        let mut distribution = self.clone();
+        distribution.rng.seek(index);
+        distribution.sample()
+    }
+}
+
+impl<R: CoreRng> core::fmt::Display for GeneralizedPareto<R> {
+    /// Formatter for displaying with macros like println!
+    /// * Location parameter
+    /// * Scale parameter
+    /// * Shape parameter
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "GPD(Location parameter, Scale parameter, Shape parameter) = GPD({}, {}, {})",
+            self.location, self.scale, self.shape
+        )?;
+        Ok(())
+    }
+}
+
+impl<R: CoreRng> crate::Distribution for GeneralizedPareto<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        GeneralizedPareto::sample(self)
+    }
+}
+
+/// 平均が μ + σ / (1 - ξ) と一致し、ξ < 0 では上限 μ - σ / ξ を超えないことを確認するテスト
+#[test]
+fn test_generalized_pareto_mean() {
+    let mut generalized_pareto =
+        GeneralizedPareto::try_new(1192_u32, 10_f64, 2_f64, 0.25_f64).unwrap();
+    let mean: f64 = (0..100_000)
+        .map(|_| generalized_pareto.sample())
+        .sum::<f64>()
+        / 100_000_f64;
+    assert!((mean - 10_f64 - 2_f64 / 0.75_f64).abs() < 0.05_f64);
+
+    generalized_pareto
+        .try_set_params(0_f64, 1_f64, -0.5_f64)
+        .unwrap();
+    let mean: f64 = (0..100_000)
+        .map(|_| generalized_pareto.sample())
+        .sum::<f64>()
+        / 100_000_f64;
+    assert!((mean - 1_f64 / 1.5_f64).abs() < 0.01_f64);
+    assert!((0..1_000).all(|_| generalized_pareto.sample() <= 2_f64));
+}
@@ -1,4 +1,9 @@
-use crate::standard_distributions::{generate_random_state, xorshift160_0_to_1};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::uniform_0_to_1;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
 
 /// Geometric Distribution
 ///
@@ -12,30 +17,132 @@ use crate::standard_distributions::{generate_random_state, xorshift160_0_to_1};
 ///
 /// // Updating the probability parameter
 /// let probability: f64 = 0.8f64;
-/// let result: Result<f64, &str> = geometric.try_set_params(probability);
+/// let result: Result<f64, rand_simple::ParameterUpdateError> = geometric.try_set_params(probability);
 /// assert_eq!(format!("{geometric}"), "Geometric(Probability) = Geometric(0.8)");
 /// println!("Number of trials until success with probability θ = {} -> {}", probability, geometric.sample());
 /// ```
-pub struct Geometric {
-    xyzuv: [u32; 5],  // 状態変数
+#[derive(Debug, Clone, PartialEq)]
+pub struct Geometric<R = Xorshift160> {
+    rng: R,           // 状態変数
     probability: f64, // 発生確率
 }
 
+/// Parameters of the [`Geometric`] distribution, returned by [`Geometric::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeometricParams {
+    /// Probability of occurrence
+    pub probability: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl Geometric {
     /// コンストラクタ
     /// * `_seed` - 乱数の種
     pub fn new(_seed: u32) -> Self {
-        let xyzuv: [u32; 5] = generate_random_state(_seed);
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `probability` - Probability of occurrence
+    pub fn try_new(seed: u32, probability: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(probability)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [u32; 5],
+        params: GeometricParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.probability)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Geometric");
+        encoder.write(&params.probability);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Geometric")?;
+        let params = GeometricParams {
+            probability: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Geometric<R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
         Self {
-            xyzuv,
+            rng,
             probability: 0.5_f64,
         }
     }
 
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
+    }
+
     /// ある確率の事象が初めて生じるまでの試行回数を返す
     pub fn sample(&mut self) -> u64 {
         let mut x: u64 = 1_u64;
-        while xorshift160_0_to_1(&mut self.xyzuv) > self.probability {
+        while uniform_0_to_1(&mut self.rng) > self.probability {
             x += 1_u64;
         }
         x
@@ -43,17 +150,46 @@ impl Geometric {
 
     /// 確率変数のパラメータを変更する
     /// * `probability` - 発生確率
-    pub fn try_set_params(&mut self, probability: f64) -> Result<f64, &str> {
-        if !(0_f64..=1_f64).contains(&probability) {
-            Err("発生確率が0より小さいか、1よりも大きいです。確率変数のパラメータは前回の設定を維持します。")
+    pub fn try_set_params(&mut self, probability: f64) -> Result<f64, ParameterUpdateError> {
+        if !probability.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "probability",
+                value: probability,
+            })
+        } else if !(0_f64..=1_f64).contains(&probability) {
+            Err(ParameterUpdateError::OutOfRange {
+                name: "probability",
+                value: probability,
+                min: 0_f64,
+                max: 1_f64,
+            })
         } else {
             self.probability = probability;
             Ok(probability)
         }
     }
+
+    /// Returns the probability of occurrence.
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> GeometricParams {
+        GeometricParams {
+            probability: self.probability(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for Geometric<R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
 }
 
-impl core::fmt::Display for Geometric {
+impl<R: CoreRng> core::fmt::Display for Geometric<R> {
     /// Formatter for displaying in functions like println! macro
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(
@@ -64,3 +200,12 @@ impl core::fmt::Display for Geometric {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for Geometric<R> {
+    type Output = u64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> u64 {
+        Geometric::sample(self)
+    }
+}
@@ -1,4 +1,9 @@
-use crate::standard_distributions::{generate_random_state, standard_exponential};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::standard_exponential;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, RandomAccessRng, SeedSequence,
+    SplittableDistribution, SplittableRng, Xorshift160,
+};
 
 /// Gunbel Distribution
 /// # Examples
@@ -10,16 +15,27 @@ use crate::standard_distributions::{generate_random_state, standard_exponential}
 /// // If you want to change the parameters of the random variable
 /// let location: f64 = 3_f64;
 /// let scale: f64 = 1.5_f64;
-/// let result: Result<(f64, f64), &str> = gunbel.try_set_params(location, scale);
+/// let result: Result<(f64, f64), rand_simple::ParameterUpdateError> = gunbel.try_set_params(location, scale);
 /// assert_eq!(format!("{gunbel}"), "Gu(Location parameter, Scale parameter) = Gu(3, 1.5)");
 /// println!("Returns a random number -> {}", gunbel.sample());
 /// ```
-pub struct Gunbel {
-    xyzuv: [u32; 5], // 状態変数
-    location: f64,   // 位置母数
-    scale: f64,      // 尺度母数
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gunbel<R = Xorshift160> {
+    rng: R,        // 状態変数
+    location: f64, // 位置母数
+    scale: f64,    // 尺度母数
 }
 
+/// Parameters of the [`Gunbel`] distribution, returned by [`Gunbel::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GunbelParams {
+    /// Location parameter
+    pub location: f64,
+    /// Scale parameter
+    pub scale: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl Gunbel {
     /// Constructor for the Gunbel distribution.
     /// # Arguments
@@ -30,13 +46,107 @@ impl Gunbel {
     /// println!("Gunbel distribution initialized with seed 1192");
     /// ```
     pub fn new(_seed: u32) -> Self {
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `location` - Location parameter
+    /// * `scale` - Scale parameter
+    pub fn try_new(seed: u32, location: f64, scale: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(location, scale)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(state: [u32; 5], params: GunbelParams) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.location, params.scale)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Gunbel");
+        encoder.write(&params.location);
+        encoder.write(&params.scale);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Gunbel")?;
+        let params = GunbelParams {
+            location: decoder.read()?,
+            scale: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Gunbel<R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
         Self {
-            xyzuv: generate_random_state(_seed),
+            rng,
             location: 0_f64,
             scale: 1_f64,
         }
     }
 
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
+    }
+
     /// Calculate a random number from the distribution.
     /// The method used is Algorithm 3.53: Inverse Transform Sampling.
     /// # Examples
@@ -47,7 +157,7 @@ impl Gunbel {
     /// ```
     pub fn sample(&mut self) -> f64 {
         loop {
-            let z = standard_exponential(&mut self.xyzuv);
+            let z = standard_exponential(&mut self.rng);
             if z > 0_f64 {
                 return -z.ln() * self.scale + self.location;
             }
@@ -65,18 +175,72 @@ impl Gunbel {
     /// let mut gunbel = rand_simple::Gunbel::new(1192u32);
     /// let location: f64 = 2_f64;
     /// let scale: f64 = 1.5_f64;
-    /// let result: Result<(f64, f64), &str> = gunbel.try_set_params(location, scale);
+    /// let result: Result<(f64, f64), rand_simple::ParameterUpdateError> = gunbel.try_set_params(location, scale);
     /// assert_eq!(format!("{gunbel}"), "Gu(Location parameter, Scale parameter) = Gu(2, 1.5)");
     /// ```
-    pub fn try_set_params(&mut self, location: f64, scale: f64) -> Result<(f64, f64), &str> {
-        if scale <= 0_f64 {
-            Err("Scale parameter is less than or equal to zero. The parameters are maintained from the previous setting.")
+    pub fn try_set_params(
+        &mut self,
+        location: f64,
+        scale: f64,
+    ) -> Result<(f64, f64), ParameterUpdateError> {
+        if !location.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "location",
+                value: location,
+            })
+        } else if !scale.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "scale",
+                value: scale,
+            })
+        } else if scale <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "scale",
+                value: scale,
+            })
         } else {
             self.location = location;
             self.scale = scale;
             Ok((location, scale))
         }
     }
+
+    /// Returns the location parameter.
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> GunbelParams {
+        GunbelParams {
+            location: self.location(),
+            scale: self.scale(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for Gunbel<R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
+}
+
+impl<R: RandomAccessRng> Gunbel<R> {
+    /// Returns the random number at `index` of the stream without generating the preceding ones,
+    /// leaving the state of `self` unchanged.
+    /// It equals the result of the `index`-th call (counting from 0) of `sample` from the start of the stream,
+    /// unless an earlier call rejected a uniform random number, which happens with probability about 2^-32 per call.
+    pub fn sample_at(&self, index: u128) -> f64 {
+        let mut distribution = self.clone();
+        distribution.rng.seek(index);
+        distribution.sample()
+    }
 }
 
 /// Gunbel Distribution
@@ -85,7 +249,7 @@ impl Gunbel {
 /// * Struct type
 /// * Location parameter
 /// * Scale parameter
-impl std::fmt::Display for Gunbel {
+impl<R: CoreRng> std::fmt::Display for Gunbel<R> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
@@ -95,3 +259,12 @@ impl std::fmt::Display for Gunbel {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for Gunbel<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        Gunbel::sample(self)
+    }
+}
@@ -1,5 +1,8 @@
-use crate::standard_distributions::{
-    generate_random_state, xorshift160_0_or_greater_and_less_than_1,
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::uniform_0_or_greater_and_less_than_1;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, RandomAccessRng, SeedSequence,
+    SplittableDistribution, SplittableRng, Xorshift160,
 };
 
 /// Half Cauchy Distribution
@@ -16,16 +19,25 @@ use crate::standard_distributions::{
 /// // Modify the distribution's scale parameter
 /// let scale: f64 = 1.5;
 /// // Update the scale parameter and generate a random number following the modified Half-Cauchy distribution
-/// let result: Result<f64, &str> = half_cauchy.try_set_params(scale);
+/// let result: Result<f64, rand_simple::ParameterUpdateError> = half_cauchy.try_set_params(scale);
 /// // Ensure that the distribution's representation includes the updated scale parameter
 /// assert_eq!(format!("{half_cauchy}"), "HCa(Scale parameter) = HCa(1.5)");
 /// println!("Returns a random number -> {}", half_cauchy.sample());
 /// ```
-pub struct HalfCauchy {
-    xyzuv: [u32; 5], // 状態変数
-    scale: f64,      // 尺度母数
+#[derive(Debug, Clone, PartialEq)]
+pub struct HalfCauchy<R = Xorshift160> {
+    rng: R,     // 状態変数
+    scale: f64, // 尺度母数
 }
 
+/// Parameters of the [`HalfCauchy`] distribution, returned by [`HalfCauchy::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HalfCauchyParams {
+    /// Scale parameter
+    pub scale: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl HalfCauchy {
     /// Constructor
     ///
@@ -38,10 +50,101 @@ impl HalfCauchy {
     /// # Returns
     /// A new instance of the HalfCauchy distribution.
     pub fn new(_seed: u32) -> Self {
-        Self {
-            xyzuv: generate_random_state(_seed),
-            scale: 1_f64,
-        }
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `scale` - Scale parameter
+    pub fn try_new(seed: u32, scale: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(scale)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [u32; 5],
+        params: HalfCauchyParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.scale)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("HalfCauchy");
+        encoder.write(&params.scale);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "HalfCauchy")?;
+        let params = HalfCauchyParams {
+            scale: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> HalfCauchy<R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
+        Self { rng, scale: 1_f64 }
+    }
+
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
     }
 
     /// Calculate a random number.
@@ -53,8 +156,7 @@ impl HalfCauchy {
     /// # Returns
     /// * `f64` - A random number following the Cauchy distribution scaled by the `scale` parameter.
     pub fn sample(&mut self) -> f64 {
-        (std::f64::consts::PI * xorshift160_0_or_greater_and_less_than_1(&mut self.xyzuv) / 2_f64)
-            .tan()
+        (std::f64::consts::PI * uniform_0_or_greater_and_less_than_1(&mut self.rng) / 2_f64).tan()
             * self.scale
     }
 
@@ -68,18 +170,57 @@ impl HalfCauchy {
     /// * `scale` - The new scale parameter to set for the random variable.
     ///
     /// # Returns
-    /// * `Result<f64, &str>` - A `Result` indicating success with the new scale value or an error message.
-    pub fn try_set_params(&mut self, scale: f64) -> Result<f64, &'static str> {
-        if scale <= 0_f64 {
-            Err("The scale parameter is less than or equal to zero. The parameters of the random variable remain unchanged.")
+    /// * `Result<f64, rand_simple::ParameterUpdateError>` - A `Result` indicating success with the new scale value or an error message.
+    pub fn try_set_params(&mut self, scale: f64) -> Result<f64, ParameterUpdateError> {
+        if !scale.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "scale",
+                value: scale,
+            })
+        } else if scale <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "scale",
+                value: scale,
+            })
         } else {
             self.scale = scale;
             Ok(scale)
         }
     }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> HalfCauchyParams {
+        HalfCauchyParams {
+            scale: self.scale(),
+        }
+    }
 }
 
-impl core::fmt::Display for HalfCauchy {
+impl<R: SplittableRng> SplittableDistribution for HalfCauchy<R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
+}
+
+impl<R: RandomAccessRng> HalfCauchy<R> {
+    /// Returns the random number at `index` of the stream without generating the preceding ones,
+    /// leaving the state of `self` unchanged.
+    /// It equals the result of the `index`-th call (counting from 0) of `sample` from the start of the stream,
+    /// unless an earlier call rejected a uniform random number, which happens with probability about 2^-32 per call.
+    pub fn sample_at(&self, index: u128) -> f64 {
+        let mut distribution = self.clone();
+        distribution.rng.seek(index);
+        distribution.sample()
+    }
+}
+
+impl<R: CoreRng> core::fmt::Display for HalfCauchy<R> {
     /// Formatter for displaying in println! and similar macros.
     /// * Scale parameter
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -87,3 +228,12 @@ impl core::fmt::Display for HalfCauchy {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for HalfCauchy<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        HalfCauchy::sample(self)
+    }
+}
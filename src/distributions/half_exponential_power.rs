@@ -0,0 +1,339 @@
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::{standard_half_exponential_power, MIN_SHAPE_EXPONENTIAL_POWER};
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
+
+/// Half Exponential Power Distribution
+///
+/// The [`crate::ExponentialPower`] distribution folded at its location: the density is proportional to
+/// `exp(-((x - μ) / σ)^p)` for `x ≥ μ`. Shape 1 gives the exponential distribution shifted by μ,
+/// and shape 2 the half normal distribution with standard deviation σ/√2.
+/// # Example
+/// ```
+/// let mut half_exponential_power = rand_simple::HalfExponentialPower::new([1192_u32, 765_u32, 1543_u32]);
+/// assert_eq!(format!("{half_exponential_power}"), "HEP(Location parameter, Scale parameter, Shape parameter) = HEP(0, 1, 2)");
+/// println!("Returns a random number -> {}", half_exponential_power.sample());
+///
+/// // If you want to change the parameters of the random variable
+/// let location: f64 = -1_f64;
+/// let scale: f64 = 2_f64;
+/// let shape: f64 = 1.5_f64;
+/// let result: Result<(f64, f64, f64), rand_simple::ParameterUpdateError> = half_exponential_power.try_set_params(location, scale, shape);
+/// assert_eq!(format!("{half_exponential_power}"), "HEP(Location parameter, Scale parameter, Shape parameter) = HEP(-1, 2, 1.5)");
+/// println!("Returns a random number -> {}", half_exponential_power.sample());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HalfExponentialPower<R = Xorshift160> {
+    rng_u_gamma: R,   // ガンマ分布の乱数を計算するための状態変数
+    rng_n_0_gamma: R, // ガンマ分布の乱数を計算するための状態変数
+    rng_n_1_gamma: R, // ガンマ分布の乱数を計算するための状態変数
+
+    location: f64, // 位置母数
+    scale: f64,    // 尺度母数
+    shape: f64,    // 形状母数
+}
+
+/// Parameters of the [`HalfExponentialPower`] distribution, returned by [`HalfExponentialPower::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HalfExponentialPowerParams {
+    /// Location parameter
+    pub location: f64,
+    /// Scale parameter
+    pub scale: f64,
+    /// Shape parameter
+    pub shape: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
+impl HalfExponentialPower {
+    /// Constructor
+    /// * `seeds` - Seeds for random number generation. Adjusted on the constructor side to ensure they are not the same.
+    pub fn new(seeds: [u32; 3]) -> Self {
+        let adjusted_seeds = crate::adjust_seeds!(seeds);
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `location` - Location parameter
+    /// * `scale` - Scale parameter
+    /// * `shape` - Shape parameter
+    pub fn try_new(
+        seeds: [u32; 3],
+        location: f64,
+        scale: f64,
+        shape: f64,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(location, scale, shape)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 3] {
+        [
+            self.rng_u_gamma.state(),
+            self.rng_n_0_gamma.state(),
+            self.rng_n_1_gamma.state(),
+        ]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 3],
+        params: HalfExponentialPowerParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.location, params.scale, params.shape)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("HalfExponentialPower");
+        encoder.write(&params.location);
+        encoder.write(&params.scale);
+        encoder.write(&params.shape);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "HalfExponentialPower")?;
+        let params = HalfExponentialPowerParams {
+            location: decoder.read()?,
+            scale: decoder.read()?,
+            shape: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> HalfExponentialPower<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 3]) -> Self {
+        let [rng_u_gamma, rng_n_0_gamma, rng_n_1_gamma] = rngs;
+        Self {
+            rng_u_gamma,
+            rng_n_0_gamma,
+            rng_n_1_gamma,
+            location: 0_f64,
+            scale: 1_f64,
+            shape: 2_f64,
+        }
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
+    /// Returns a random number.
+    pub fn sample(&mut self) -> f64 {
+        let y: f64 = standard_half_exponential_power(
+            &mut self.rng_u_gamma,
+            &mut self.rng_n_0_gamma,
+            &mut self.rng_n_1_gamma,
+            &self.shape,
+        );
+        self.location + y * self.scale
+    }
+
+    /// Modify the parameters of the random variable.
+    /// * `location` - Location parameter
+    /// * `scale` - Scale parameter
+    /// * `shape` - Shape parameter, at least 0.01; smaller shapes give values beyond the range of `f64`
+    pub fn try_set_params(
+        &mut self,
+        location: f64,
+        scale: f64,
+        shape: f64,
+    ) -> Result<(f64, f64, f64), ParameterUpdateError> {
+        if !location.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "location",
+                value: location,
+            })
+        } else if !scale.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "scale",
+                value: scale,
+            })
+        } else if scale <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "scale",
+                value: scale,
+            })
+        } else if !shape.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "shape",
+                value: shape,
+            })
+        } else if shape <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "shape",
+                value: shape,
+            })
+        } else if shape < MIN_SHAPE_EXPONENTIAL_POWER {
+            Err(ParameterUpdateError::OutOfRange {
+                name: "shape",
+                value: shape,
+                min: MIN_SHAPE_EXPONENTIAL_POWER,
+                max: f64::INFINITY,
+            })
+        } else {
+            self.location = location;
+            self.scale = scale;
+            self.shape = shape;
+            Ok((location, scale, shape))
+        }
+    }
+
+    /// Returns the location parameter.
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the shape parameter.
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> HalfExponentialPowerParams {
+        HalfExponentialPowerParams {
+            location: self.location(),
+            scale: self.scale(),
+            shape: self.shape(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for HalfExponentialPower<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [
+            &mut self.rng_u_gamma,
+            &mut self.rng_n_0_gamma,
+            &mut self.rng_n_1_gamma,
+        ] {
+            rng.jump_substream();
+        }
+    }
+}
+
+impl<R: CoreRng> core::fmt::Display for HalfExponentialPower<R> {
+    /// Formatter for displaying in macros like print!
+    /// * Location parameter
+    /// * Scale parameter
+    /// * Shape parameter
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "HEP(Location parameter, Scale parameter, Shape parameter) = HEP({}, {}, {})",
+            self.location, self.scale, self.shape
+        )?;
+        Ok(())
+    }
+}
+
+impl<R: CoreRng> crate::Distribution for HalfExponentialPower<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        HalfExponentialPower::sample(self)
+    }
+}
+
+/// 平均が σ Γ(2/p) / Γ(1/p) になることを確認するテスト
+/// (p = 1 で指数分布の 1、p = 2 で半正規分布の 1/√π、p → ∞ で区間 [0, 1] の一様分布の 1/2)
+#[test]
+fn test_half_exponential_power() {
+    for (shape, expected) in [
+        (1_f64, 1_f64),
+        (2_f64, 1_f64 / std::f64::consts::PI.sqrt()),
+        (1_000_f64, 0.5_f64),
+    ] {
+        let mut half_exponential_power =
+            HalfExponentialPower::try_new([1192_u32, 765_u32, 1543_u32], 0_f64, 1_f64, shape)
+                .unwrap();
+        let samples: Vec<f64> = (0..100_000)
+            .map(|_| half_exponential_power.sample())
+            .collect();
+        let mean: f64 = samples.iter().sum::<f64>() / 100_000_f64;
+        assert!((mean - expected).abs() < 0.02_f64 * expected);
+        // 形状母数が大きくても、0 にアンダーフローしない
+        assert!(samples.iter().all(|x| *x > 0_f64));
+    }
+
+    // 形状母数が小さい場合、値は非常に大きくなるが有限に留まる
+    let mut half_exponential_power = HalfExponentialPower::try_new(
+        [1192_u32, 765_u32, 1543_u32],
+        0_f64,
+        1_f64,
+        MIN_SHAPE_EXPONENTIAL_POWER,
+    )
+    .unwrap();
+    assert!((0..1_000).all(|_| half_exponential_power.sample().is_finite()));
+    assert_eq!(
+        half_exponential_power.try_set_params(0_f64, 1_f64, 0.001_f64),
+        Err(ParameterUpdateError::OutOfRange {
+            name: "shape",
+            value: 0.001_f64,
+            min: MIN_SHAPE_EXPONENTIAL_POWER,
+            max: f64::INFINITY,
+        })
+    );
+    assert_eq!(half_exponential_power.shape(), MIN_SHAPE_EXPONENTIAL_POWER);
+}
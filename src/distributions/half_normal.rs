@@ -1,4 +1,9 @@
-use crate::standard_distributions::{generate_random_state, standard_normal};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::standard_normal;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
 
 /// Half Normal Distribution
 /// # Example
@@ -9,46 +14,178 @@ use crate::standard_distributions::{generate_random_state, standard_normal};
 ///
 /// // If you want to change the parameters of the random variable
 /// let variance: f64 = 2_f64;
-/// let result: Result<f64, &str> = half_normal.try_set_params(variance);
+/// let result: Result<f64, rand_simple::ParameterUpdateError> = half_normal.try_set_params(variance);
 /// assert_eq!(format!("{half_normal}"), "HN(Std^2) = HN(2^2)");
 /// println!("Returns a random number -> {}", half_normal.sample());
 /// ```
-pub struct HalfNormal {
-    xyzuv0: [u32; 5], // 状態変数
-    xyzuv1: [u32; 5], // 状態変数
-    std: f64,         // 標準偏差
+#[derive(Debug, Clone, PartialEq)]
+pub struct HalfNormal<R = Xorshift160> {
+    rng0: R,  // 状態変数
+    rng1: R,  // 状態変数
+    std: f64, // 標準偏差
 }
 
+/// Parameters of the [`HalfNormal`] distribution, returned by [`HalfNormal::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HalfNormalParams {
+    /// Standard deviation
+    pub std: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl HalfNormal {
     /// Constructor
     /// * `seeds` - Seeds for random number generation. Adjusted on the constructor side to ensure they are not the same.
     pub fn new(seeds: [u32; 2]) -> Self {
         let adjusted_seeds = crate::adjust_seeds!(seeds);
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `std` - Standard deviation
+    pub fn try_new(seeds: [u32; 2], std: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(std)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 2] {
+        [self.rng0.state(), self.rng1.state()]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 2],
+        params: HalfNormalParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.std)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("HalfNormal");
+        encoder.write(&params.std);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "HalfNormal")?;
+        let params = HalfNormalParams {
+            std: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> HalfNormal<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 2]) -> Self {
+        let [rng0, rng1] = rngs;
         Self {
-            xyzuv0: generate_random_state(adjusted_seeds[0]),
-            xyzuv1: generate_random_state(adjusted_seeds[1]),
+            rng0,
+            rng1,
             std: 1_f64,
         }
     }
 
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
     /// Generate a random number.
     pub fn sample(&mut self) -> f64 {
-        standard_normal(&mut self.xyzuv0, &mut self.xyzuv1).abs() * self.std
+        standard_normal(&mut self.rng0, &mut self.rng1).abs() * self.std
     }
 
     /// Modify the parameters of the random variable.
     /// * `std` - Standard deviation
-    pub fn try_set_params(&mut self, std: f64) -> Result<f64, &'static str> {
-        if std <= 0_f64 {
-            Err("Standard deviation is less than or equal to 0. The random variable's parameters will remain unchanged.")
+    pub fn try_set_params(&mut self, std: f64) -> Result<f64, ParameterUpdateError> {
+        if !std.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "std",
+                value: std,
+            })
+        } else if std <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "std",
+                value: std,
+            })
         } else {
             self.std = std;
             Ok(std)
         }
     }
+
+    /// Returns the standard deviation.
+    pub fn std(&self) -> f64 {
+        self.std
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> HalfNormalParams {
+        HalfNormalParams { std: self.std() }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for HalfNormal<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [&mut self.rng0, &mut self.rng1] {
+            rng.jump_substream();
+        }
+    }
 }
 
-impl core::fmt::Display for HalfNormal {
+impl<R: CoreRng> core::fmt::Display for HalfNormal<R> {
     /// Formatter for displaying in functions like println! macro
     /// * Standard deviation
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -56,3 +193,12 @@ impl core::fmt::Display for HalfNormal {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for HalfNormal<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        HalfNormal::sample(self)
+    }
+}
@@ -1,4 +1,9 @@
-use crate::standard_distributions::{generate_random_state, standard_normal, xorshift160_0_to_1};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::{standard_normal, uniform_0_to_1};
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
 
 /// Represents an Inverse Gaussian (IG) distribution.
 ///
@@ -15,21 +20,32 @@ use crate::standard_distributions::{generate_random_state, standard_normal, xors
 /// // Modify the distribution parameters
 /// let mean: f64 = 1.5f64;
 /// let shape: f64 = 2f64;
-/// let result: Result<(f64, f64), &str> = inverse_gaussian.try_set_params(mean, shape);
+/// let result: Result<(f64, f64), rand_simple::ParameterUpdateError> = inverse_gaussian.try_set_params(mean, shape);
 /// assert_eq!(format!("{inverse_gaussian}"), "IG(Mean, Shape) = IG(1.5, 2)");
 /// println!(
 ///     "Generate a random number from the Inverse Gaussian distribution with mean μ = {}, shape λ = {} -> {}",
 ///     mean, shape, inverse_gaussian.sample()
 /// );
 /// ```
-pub struct InverseGaussian {
-    xyzuv_u: [u32; 5],    // 状態変数
-    xyzuv_hn_0: [u32; 5], // 状態変数
-    xyzuv_hn_1: [u32; 5], // 状態変数
-    mean: f64,            // 平均
-    shape: f64,           // 形状母数
+#[derive(Debug, Clone, PartialEq)]
+pub struct InverseGaussian<R = Xorshift160> {
+    rng_u: R,    // 状態変数
+    rng_hn_0: R, // 状態変数
+    rng_hn_1: R, // 状態変数
+    mean: f64,   // 平均
+    shape: f64,  // 形状母数
 }
 
+/// Parameters of the [`InverseGaussian`] distribution, returned by [`InverseGaussian::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InverseGaussianParams {
+    /// Mean
+    pub mean: f64,
+    /// Shape parameter
+    pub shape: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl InverseGaussian {
     /// Constructs a new `InverseGaussian` instance.
     ///
@@ -40,18 +56,121 @@ impl InverseGaussian {
     /// # Notes
     /// - The `mean` parameter is initialized to `1.0`.
     /// - The `shape` parameter is initialized to `1.0`.
-    /// - Internal state variables (`xyzuv_u`, `xyzuv_hn_0`, `xyzuv_hn_1`) are derived from the adjusted seeds.
+    /// - Internal state variables (`rng_u`, `rng_hn_0`, `rng_hn_1`) are derived from the adjusted seeds.
     pub fn new(seeds: [u32; 3]) -> Self {
         let adjusted_seeds = crate::adjust_seeds!(seeds);
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `mean` - Mean
+    /// * `shape` - Shape parameter
+    pub fn try_new(seeds: [u32; 3], mean: f64, shape: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(mean, shape)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 3] {
+        [
+            self.rng_u.state(),
+            self.rng_hn_0.state(),
+            self.rng_hn_1.state(),
+        ]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 3],
+        params: InverseGaussianParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.mean, params.shape)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("InverseGaussian");
+        encoder.write(&params.mean);
+        encoder.write(&params.shape);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "InverseGaussian")?;
+        let params = InverseGaussianParams {
+            mean: decoder.read()?,
+            shape: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> InverseGaussian<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 3]) -> Self {
+        let [rng_u, rng_hn_0, rng_hn_1] = rngs;
         Self {
-            xyzuv_u: generate_random_state(adjusted_seeds[0]),
-            xyzuv_hn_0: generate_random_state(adjusted_seeds[1]),
-            xyzuv_hn_1: generate_random_state(adjusted_seeds[2]),
+            rng_u,
+            rng_hn_0,
+            rng_hn_1,
             mean: 1_f64,
             shape: 1_f64,
         }
     }
 
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
     /// Generates a random number following the inverse Gaussian distribution.
     ///
     /// # Algorithm
@@ -72,7 +191,7 @@ impl InverseGaussian {
         let q = p / (2_f64 * self.shape);
 
         // step 1
-        let z = standard_normal(&mut self.xyzuv_hn_0, &mut self.xyzuv_hn_1).abs();
+        let z = standard_normal(&mut self.rng_hn_0, &mut self.rng_hn_1).abs();
         if z == 0_f64 {
             // step 1 -> step 5
             self.mean
@@ -82,7 +201,7 @@ impl InverseGaussian {
             let x_1 = v + (v.powi(2) - p).sqrt();
 
             // step 3
-            let u = xorshift160_0_to_1(&mut self.xyzuv_u);
+            let u = uniform_0_to_1(&mut self.rng_u);
             if u * (x_1 + self.mean) <= self.mean {
                 // step 3 -> step 5
                 x_1
@@ -101,7 +220,7 @@ impl InverseGaussian {
     ///
     /// # Returns
     /// - `Ok((mean, shape))`: If both parameters are valid, the new values are set, and they are returned as a tuple.
-    /// - `Err(&str)`: If either parameter is invalid, an error message is returned, and the previous parameter values are retained.
+    /// - `Err(ParameterUpdateError)`: If either parameter is invalid, an error message is returned, and the previous parameter values are retained.
     ///
     /// # Validations
     /// - `mean > 0`: Ensures the mean is strictly positive. If this condition is violated, an error is returned.
@@ -122,20 +241,67 @@ impl InverseGaussian {
     /// assert!(result.is_err());
     /// println!("{}", result.unwrap_err()); // Output: "平均が0以下です..."
     /// ```
-    pub fn try_set_params(&mut self, mean: f64, shape: f64) -> Result<(f64, f64), &str> {
-        if mean <= 0_f64 {
-            Err("Mean is less than or equal to 0. The previous parameter values are retained.")
+    pub fn try_set_params(
+        &mut self,
+        mean: f64,
+        shape: f64,
+    ) -> Result<(f64, f64), ParameterUpdateError> {
+        if !mean.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "mean",
+                value: mean,
+            })
+        } else if mean <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "mean",
+                value: mean,
+            })
+        } else if !shape.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "shape",
+                value: shape,
+            })
         } else if shape <= 0_f64 {
-            Err("Shape parameter is less than or equal to 0. The previous parameter values are retained.")
+            Err(ParameterUpdateError::NonPositive {
+                name: "shape",
+                value: shape,
+            })
         } else {
             self.mean = mean;
             self.shape = shape;
             core::result::Result::Ok((mean, shape))
         }
     }
+
+    /// Returns the mean.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the shape parameter.
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> InverseGaussianParams {
+        InverseGaussianParams {
+            mean: self.mean(),
+            shape: self.shape(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for InverseGaussian<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [&mut self.rng_u, &mut self.rng_hn_0, &mut self.rng_hn_1] {
+            rng.jump_substream();
+        }
+    }
 }
 
-impl core::fmt::Display for InverseGaussian {
+impl<R: CoreRng> core::fmt::Display for InverseGaussian<R> {
     /// Formatter for displaying in functions like println! macro
     /// * Mean
     /// * Standard deviation
@@ -144,3 +310,12 @@ impl core::fmt::Display for InverseGaussian {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for InverseGaussian<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        InverseGaussian::sample(self)
+    }
+}
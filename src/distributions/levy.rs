@@ -1,4 +1,9 @@
-use crate::standard_distributions::{generate_random_state, standard_normal};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::standard_normal;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
 
 /// Lévy Distribution
 /// # Example
@@ -12,34 +17,148 @@ use crate::standard_distributions::{generate_random_state, standard_normal};
 /// let location: f64 = -2_f64;
 /// let scale: f64 = 1.5_f64;
 /// // Update the parameters and generate a random number following the modified Levy distribution
-/// let result: Result<(f64, f64), &str> = levy.try_set_params(location, scale);
+/// let result: Result<(f64, f64), rand_simple::ParameterUpdateError> = levy.try_set_params(location, scale);
 /// assert_eq!(format!("{levy}"), "Lévy(Location parameter, Scale parameter) = Lévy(-2, 1.5)");
 /// println!("Returns a random number following a Levy distribution with location μ = {} and scale θ = {} -> {}", location, scale, levy.sample());
 /// ```
-pub struct Levy {
-    xyzuv0: [u32; 5], // 状態変数
-    xyzuv1: [u32; 5], // 状態変数
-    location: f64,    // 位置母数
-    scale: f64,       // 尺度母数
+#[derive(Debug, Clone, PartialEq)]
+pub struct Levy<R = Xorshift160> {
+    rng0: R,       // 状態変数
+    rng1: R,       // 状態変数
+    location: f64, // 位置母数
+    scale: f64,    // 尺度母数
 }
 
+/// Parameters of the [`Levy`] distribution, returned by [`Levy::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevyParams {
+    /// Location parameter
+    pub location: f64,
+    /// Scale parameter
+    pub scale: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl Levy {
     /// Constructor
     /// * `seeds` - Random number seeds. Adjusted internally to ensure uniqueness.
     pub fn new(seeds: [u32; 2_usize]) -> Self {
         let adjusted_seeds = crate::adjust_seeds!(seeds);
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `location` - Location parameter
+    /// * `scale` - Scale parameter
+    pub fn try_new(
+        seeds: [u32; 2_usize],
+        location: f64,
+        scale: f64,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(location, scale)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 2] {
+        [self.rng0.state(), self.rng1.state()]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 2],
+        params: LevyParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.location, params.scale)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Levy");
+        encoder.write(&params.location);
+        encoder.write(&params.scale);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Levy")?;
+        let params = LevyParams {
+            location: decoder.read()?,
+            scale: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Levy<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 2]) -> Self {
+        let [rng0, rng1] = rngs;
         Self {
-            xyzuv0: generate_random_state(adjusted_seeds[0]),
-            xyzuv1: generate_random_state(adjusted_seeds[1]),
+            rng0,
+            rng1,
             location: 0_f64,
             scale: 1_f64,
         }
     }
 
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
     /// Generate a random number.
     pub fn sample(&mut self) -> f64 {
         loop {
-            let z = standard_normal(&mut self.xyzuv0, &mut self.xyzuv1).abs();
+            let z = standard_normal(&mut self.rng0, &mut self.rng1).abs();
             if z > 0_f64 {
                 return z.powi(-2_i32) * self.scale + self.location;
             }
@@ -53,18 +172,58 @@ impl Levy {
         &mut self,
         location: f64,
         scale: f64,
-    ) -> Result<(f64, f64), &'static str> {
-        if scale <= 0_f64 {
-            Err("The scale parameter is less than or equal to 0. The parameters of the probability variable remain unchanged.")
+    ) -> Result<(f64, f64), ParameterUpdateError> {
+        if !location.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "location",
+                value: location,
+            })
+        } else if !scale.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "scale",
+                value: scale,
+            })
+        } else if scale <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "scale",
+                value: scale,
+            })
         } else {
             self.location = location;
             self.scale = scale;
             Ok((location, scale))
         }
     }
+
+    /// Returns the location parameter.
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> LevyParams {
+        LevyParams {
+            location: self.location(),
+            scale: self.scale(),
+        }
+    }
 }
 
-impl core::fmt::Display for Levy {
+impl<R: SplittableRng> SplittableDistribution for Levy<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [&mut self.rng0, &mut self.rng1] {
+            rng.jump_substream();
+        }
+    }
+}
+
+impl<R: CoreRng> core::fmt::Display for Levy<R> {
     /// Formatter for use with macros like `println!`
     /// * Location parameter
     /// * Scale parameter
@@ -77,3 +236,12 @@ impl core::fmt::Display for Levy {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for Levy<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        Levy::sample(self)
+    }
+}
@@ -0,0 +1,256 @@
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::standard_logistic;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, RandomAccessRng, SeedSequence,
+    SplittableDistribution, SplittableRng, Xorshift160,
+};
+
+/// Log-Logistic Distribution (Fisk distribution)
+///
+/// The distribution of `exp(X)` where `X` follows the [`crate::Logistic`] distribution with location μ and scale s,
+/// i.e. the Fisk distribution with scale `exp(μ)` and shape `1 / s`.
+/// # Example
+/// ```
+/// let mut log_logistic = rand_simple::LogLogistic::new(1192u32);
+/// assert_eq!(format!("{log_logistic}"), "LLo(Location parameter, Scale parameter) = LLo(0, 1)");
+/// println!("Returns a random number -> {}", log_logistic.sample());
+///
+/// // Modify the distribution's parameters
+/// let location: f64 = -2f64;
+/// let scale: f64 = 1.5f64;
+/// let result: Result<(f64, f64), rand_simple::ParameterUpdateError> = log_logistic.try_set_params(location, scale);
+/// assert_eq!(format!("{log_logistic}"), "LLo(Location parameter, Scale parameter) = LLo(-2, 1.5)");
+/// println!("Returns a random number -> {}", log_logistic.sample());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogLogistic<R = Xorshift160> {
+    rng: R,        // 状態変数
+    location: f64, // 位置母数
+    scale: f64,    // 尺度母数
+}
+
+/// Parameters of the [`LogLogistic`] distribution, returned by [`LogLogistic::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogLogisticParams {
+    /// Location parameter
+    pub location: f64,
+    /// Scale parameter
+    pub scale: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
+impl LogLogistic {
+    /// Constructor
+    /// * `_seed` - Random seed to initialize the generator.
+    pub fn new(_seed: u32) -> Self {
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `location` - Location parameter
+    /// * `scale` - Scale parameter
+    pub fn try_new(seed: u32, location: f64, scale: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(location, scale)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [u32; 5],
+        params: LogLogisticParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.location, params.scale)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("LogLogistic");
+        encoder.write(&params.location);
+        encoder.write(&params.scale);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "LogLogistic")?;
+        let params = LogLogisticParams {
+            location: decoder.read()?,
+            scale: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> LogLogistic<R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
+        Self {
+            rng,
+            location: 0_f64,
+            scale: 1_f64,
+        }
+    }
+
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
+    }
+
+    /// Calculate a random number.
+    pub fn sample(&mut self) -> f64 {
+        // ロジスティック分布に従う乱数の指数を返す
+        (standard_logistic(&mut self.rng) * self.scale + self.location).exp()
+    }
+
+    /// Modify the parameters of the random variable.
+    /// * `location` - Location parameter
+    /// * `scale` - Scale parameter
+    pub fn try_set_params(
+        &mut self,
+        location: f64,
+        scale: f64,
+    ) -> Result<(f64, f64), ParameterUpdateError> {
+        if !location.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "location",
+                value: location,
+            })
+        } else if !scale.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "scale",
+                value: scale,
+            })
+        } else if scale <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "scale",
+                value: scale,
+            })
+        } else {
+            self.location = location;
+            self.scale = scale;
+            Ok((location, scale))
+        }
+    }
+
+    /// Returns the location parameter.
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> LogLogisticParams {
+        LogLogisticParams {
+            location: self.location(),
+            scale: self.scale(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for LogLogistic<R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
+}
+
+impl<R: RandomAccessRng> LogLogistic<R> {
+    /// Returns the random number at `index` of the stream without generating the preceding ones,
+    /// leaving the state of `self` unchanged.
+    /// It equals the result of the `index`-th call (counting from 0) of `sample` from the start of the stream,
+    /// unless an earlier call rejected a uniform random number, which happens with probability about 2^-32 per call.
+    pub fn sample_at(&self, index: u128) -> f64 {
+        let mut distribution = self.clone();
+        distribution.rng.seek(index);
+        distribution.sample()
+    }
+}
+
+/// Formatter for displaying with macros like println!
+/// * Location parameter
+/// * Scale parameter
+impl<R: CoreRng> core::fmt::Display for LogLogistic<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "LLo(Location parameter, Scale parameter) = LLo({}, {})",
+            self.location, self.scale
+        )?;
+        Ok(())
+    }
+}
+
+impl<R: CoreRng> crate::Distribution for LogLogistic<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        LogLogistic::sample(self)
+    }
+}
+
+/// 同じ種と母数のロジスティック分布に従う乱数の指数と一致することを確認するテスト
+#[test]
+fn test_log_logistic_exp() {
+    let mut logistic = crate::Logistic::try_new(1192_u32, 1_f64, 2_f64).unwrap();
+    let mut log_logistic = LogLogistic::try_new(1192_u32, 1_f64, 2_f64).unwrap();
+    for _ in 0..100 {
+        let expected: f64 = logistic.sample().exp();
+        assert!((log_logistic.sample() - expected).abs() <= 1e-12_f64 * expected);
+    }
+}
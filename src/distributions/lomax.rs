@@ -0,0 +1,257 @@
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::uniform_greater_than_0_and_less_than_1;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, RandomAccessRng, SeedSequence,
+    SplittableDistribution, SplittableRng, Xorshift160,
+};
+
+/// Lomax Distribution (Pareto Type II)
+///
+/// The density is `(α / λ) (1 + x / λ)^-(α + 1)` for `x ≥ 0`, with shape α and scale λ:
+/// the [`crate::Pareto`] distribution shifted to start at zero. It is sampled by inversion.
+/// # Example
+/// ```
+/// let mut lomax = rand_simple::Lomax::new(1192_u32);
+/// assert_eq!(format!("{lomax}"), "Lomax(Shape parameter, Scale parameter) = Lomax(1, 1)");
+/// println!("Returns a random number -> {}", lomax.sample());
+///
+/// // If you want to change the parameters of the random variable
+/// let shape: f64 = 3_f64;
+/// let scale: f64 = 2_f64;
+/// let result: Result<(f64, f64), rand_simple::ParameterUpdateError> = lomax.try_set_params(shape, scale);
+/// assert_eq!(format!("{lomax}"), "Lomax(Shape parameter, Scale parameter) = Lomax(3, 2)");
+/// println!("Returns a random number -> {}", lomax.sample());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lomax<R = Xorshift160> {
+    rng: R,     // 状態変数
+    shape: f64, // 形状母数
+    scale: f64, // 尺度母数
+}
+
+/// Parameters of the [`Lomax`] distribution, returned by [`Lomax::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LomaxParams {
+    /// Shape parameter
+    pub shape: f64,
+    /// Scale parameter
+    pub scale: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
+impl Lomax {
+    /// Constructor
+    /// * `_seed` - Random number seed
+    pub fn new(_seed: u32) -> Self {
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `shape` - Shape parameter
+    /// * `scale` - Scale parameter
+    pub fn try_new(seed: u32, shape: f64, scale: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(shape, scale)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(state: [u32; 5], params: LomaxParams) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.shape, params.scale)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Lomax");
+        encoder.write(&params.shape);
+        encoder.write(&params.scale);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Lomax")?;
+        let params = LomaxParams {
+            shape: decoder.read()?,
+            scale: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Lomax<R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
+        Self {
+            rng,
+            shape: 1_f64,
+            scale: 1_f64,
+        }
+    }
+
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
+    }
+
+    /// Calculate random numbers.
+    pub fn sample(&mut self) -> f64 {
+        // 逆関数法: 開区間 (0, 1) の一様乱数 u から λ (u^(-1/α) - 1) を計算する
+        // u が 1 に近いときの桁落ちを避けるため、exp_m1 を使用する
+        let u: f64 = uniform_greater_than_0_and_less_than_1(&mut self.rng);
+        self.scale * (-u.ln() / self.shape).exp_m1()
+    }
+
+    /// Modify the parameters of the random variable.
+    /// * `shape` - Shape parameter
+    /// * `scale` - Scale parameter
+    pub fn try_set_params(
+        &mut self,
+        shape: f64,
+        scale: f64,
+    ) -> Result<(f64, f64), ParameterUpdateError> {
+        if !shape.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "shape",
+                value: shape,
+            })
+        } else if shape <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "shape",
+                value: shape,
+            })
+        } else if !scale.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "scale",
+                value: scale,
+            })
+        } else if scale <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "scale",
+                value: scale,
+            })
+        } else {
+            self.shape = shape;
+            self.scale = scale;
+            Ok((shape, scale))
+        }
+    }
+
+    /// Returns the shape parameter.
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> LomaxParams {
+        LomaxParams {
+            shape: self.shape(),
+            scale: self.scale(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for Lomax<R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
+}
+
+impl<R: RandomAccessRng> Lomax<R> {
+    /// Returns the random number at `index` of the stream without generating the preceding ones,
+    /// leaving the state of `self` unchanged.
+    /// It equals the result of the `index`-th call (counting from 0) of `sample` from the start of the stream,
+    /// unless an earlier call rejected a uniform random number, which happens with probability about 2^-32 per call.
+    pub fn sample_at(&self, index: u128) -> f64 {
+        let mut distribution = self.clone();
+        distribution.rng.seek(index);
+        distribution.sample()
+    }
+}
+
+impl<R: CoreRng> core::fmt::Display for Lomax<R> {
+    /// Formatter for displaying with macros like println!
+    /// * Shape parameter
+    /// * Scale parameter
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "Lomax(Shape parameter, Scale parameter) = Lomax({}, {})",
+            self.shape, self.scale
+        )?;
+        Ok(())
+    }
+}
+
+impl<R: CoreRng> crate::Distribution for Lomax<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        Lomax::sample(self)
+    }
+}
+
+/// 平均が λ / (α - 1) と一致することを確認するテスト
+#[test]
+fn test_lomax_mean() {
+    let mut lomax = Lomax::try_new(1192_u32, 3_f64, 2_f64).unwrap();
+    let mean: f64 = (0..100_000).map(|_| lomax.sample()).sum::<f64>() / 100_000_f64;
+    assert!((mean - 1_f64).abs() < 0.05_f64);
+}
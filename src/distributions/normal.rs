@@ -1,4 +1,9 @@
-use crate::standard_distributions::{generate_random_state, standard_normal};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::standard_normal;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
 
 /// Normal Distribution
 /// # Example
@@ -10,50 +15,231 @@ use crate::standard_distributions::{generate_random_state, standard_normal};
 /// // If you want to change the parameters of the random variable
 /// let mean: f64 = -3_f64;
 /// let std: f64 = 2_f64;
-/// let result: Result<(f64, f64), &str> = normal.try_set_params(mean, std);
+/// let result: Result<(f64, f64), rand_simple::ParameterUpdateError> = normal.try_set_params(mean, std);
 /// assert_eq!(format!("{normal}"), "N(Mean, Std^2) = N(-3, 2^2)");
 /// println!("Returns a random number -> {}", normal.sample());
+///
+/// // A clone copies the full generator state and reproduces the same stream
+/// let mut branch = normal.clone();
+/// assert_eq!(branch, normal);
+/// assert_eq!(branch.sample(), normal.sample());
+/// println!("{normal:?}");
 /// ```
-pub struct Normal {
-    xyzuv0: [u32; 5], // 状態変数
-    xyzuv1: [u32; 5], // 状態変数
-    mean: f64,        // 平均
-    std: f64,         // 標準偏差
+#[derive(Debug, Clone, PartialEq)]
+pub struct Normal<R = Xorshift160> {
+    rng0: R,   // 状態変数
+    rng1: R,   // 状態変数
+    mean: f64, // 平均
+    std: f64,  // 標準偏差
+}
+
+/// Parameters of the [`Normal`] distribution, returned by [`Normal::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalParams {
+    /// Mean
+    pub mean: f64,
+    /// Standard deviation
+    pub std: f64,
 }
 
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl Normal {
     /// Constructor
     /// * `seeds` - Seeds for random number generation. Adjusted on the constructor side to ensure they are not the same.
     pub fn new(seeds: [u32; 2]) -> Self {
         let adjusted_seeds = crate::adjust_seeds!(seeds);
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `mean` - Mean
+    /// * `std` - Standard deviation
+    /// # Example
+    /// ```
+    /// let normal = rand_simple::Normal::try_new([1192_u32, 765_u32], -3_f64, 2_f64).unwrap();
+    /// assert_eq!(format!("{normal}"), "N(Mean, Std^2) = N(-3, 2^2)");
+    ///
+    /// // Invalid parameters are reported instead of returning an instance
+    /// assert!(rand_simple::Normal::try_new([1192_u32, 765_u32], 0_f64, -1_f64).is_err());
+    /// ```
+    pub fn try_new(seeds: [u32; 2], mean: f64, std: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(mean, std)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 2] {
+        [self.rng0.state(), self.rng1.state()]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 2],
+        params: NormalParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.mean, params.std)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Normal");
+        encoder.write(&params.mean);
+        encoder.write(&params.std);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Normal")?;
+        let params = NormalParams {
+            mean: decoder.read()?,
+            std: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Normal<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 2]) -> Self {
+        let [rng0, rng1] = rngs;
         Self {
-            xyzuv0: generate_random_state(adjusted_seeds[0]),
-            xyzuv1: generate_random_state(adjusted_seeds[1]),
+            rng0,
+            rng1,
             mean: 0_f64,
             std: 1_f64,
         }
     }
 
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
     /// Generate a random number.
     pub fn sample(&mut self) -> f64 {
-        standard_normal(&mut self.xyzuv0, &mut self.xyzuv1) * self.std + self.mean
+        standard_normal(&mut self.rng0, &mut self.rng1) * self.std + self.mean
     }
 
     /// Modify the parameters of the random variable.
     /// * `mean` - Mean
     /// * `std` - Standard deviation
-    pub fn try_set_params(&mut self, mean: f64, std: f64) -> Result<(f64, f64), &'static str> {
-        if std <= 0_f64 {
-            Err("Standard deviation is less than or equal to 0. The random variable's parameters will remain unchanged.")
+    pub fn try_set_params(
+        &mut self,
+        mean: f64,
+        std: f64,
+    ) -> Result<(f64, f64), ParameterUpdateError> {
+        if !mean.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "mean",
+                value: mean,
+            })
+        } else if !std.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "std",
+                value: std,
+            })
+        } else if std <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "std",
+                value: std,
+            })
         } else {
             self.mean = mean;
             self.std = std;
             Ok((self.mean, self.std))
         }
     }
+
+    /// Returns the mean.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the standard deviation.
+    pub fn std(&self) -> f64 {
+        self.std
+    }
+
+    /// Returns the current parameters of the random variable.
+    /// # Example
+    /// ```
+    /// let normal = rand_simple::Normal::try_new([1192_u32, 765_u32], -3_f64, 2_f64).unwrap();
+    /// assert_eq!(normal.mean(), -3_f64);
+    /// assert_eq!(normal.std(), 2_f64);
+    ///
+    /// let params = normal.params();
+    /// assert_eq!(params, rand_simple::NormalParams { mean: -3_f64, std: 2_f64 });
+    ///
+    /// // Round trip: rebuild a generator from the stored parameters
+    /// let copied = rand_simple::Normal::try_new([1192_u32, 765_u32], params.mean, params.std).unwrap();
+    /// assert_eq!(copied.params(), params);
+    /// ```
+    pub fn params(&self) -> NormalParams {
+        NormalParams {
+            mean: self.mean(),
+            std: self.std(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for Normal<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [&mut self.rng0, &mut self.rng1] {
+            rng.jump_substream();
+        }
+    }
 }
 
-impl core::fmt::Display for Normal {
+impl<R: CoreRng> core::fmt::Display for Normal<R> {
     /// Formatter for displaying in functions like println! macro
     /// * Mean
     /// * Standard deviation
@@ -62,3 +248,12 @@ impl core::fmt::Display for Normal {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for Normal<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        Normal::sample(self)
+    }
+}
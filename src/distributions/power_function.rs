@@ -1,5 +1,8 @@
-use crate::standard_distributions::{
-    generate_random_state, xorshift160_greater_than_0_and_less_than_1,
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::uniform_greater_than_0_and_less_than_1;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, RandomAccessRng, SeedSequence,
+    SplittableDistribution, SplittableRng, Xorshift160,
 };
 
 /// Power function distribution
@@ -12,28 +15,146 @@ use crate::standard_distributions::{
 /// let shape: f64 = 2_f64;
 /// let min: f64 = -1_f64;
 /// let max: f64 = 1_f64;
-/// let result: Result<(f64, f64, f64), &str> = power_function.try_set_params(shape, min, max);
+/// let result: Result<(f64, f64, f64), rand_simple::ParameterUpdateError> = power_function.try_set_params(shape, min, max);
 /// assert_eq!(format!("{power_function}"), "PF(Shape parameter γ, Boundary parameter a, Boundary parameter b) = PF(2, -1, 1)");
 /// ```
-pub struct PowerFunction {
-    xyzuv: [u32; 5], // 状態変数
-    shape: f64,      // 形状母数
-    min_a: f64,      // 境界母数(小範)
-    max_b: f64,      // 境界母数(大範)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerFunction<R = Xorshift160> {
+    rng: R,     // 状態変数
+    shape: f64, // 形状母数
+    min_a: f64, // 境界母数(小範)
+    max_b: f64, // 境界母数(大範)
 }
 
+/// Parameters of the [`PowerFunction`] distribution, returned by [`PowerFunction::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerFunctionParams {
+    /// Shape parameter γ
+    pub shape: f64,
+    /// Lower boundary parameter a
+    pub min_a: f64,
+    /// Upper boundary parameter b
+    pub max_b: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl PowerFunction {
     /// Constructor
     /// * `_seed` - Random seed
     pub fn new(_seed: u32) -> Self {
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `shape` - Shape parameter γ
+    /// * `min_a` - Lower boundary parameter a
+    /// * `max_b` - Upper boundary parameter b
+    pub fn try_new(
+        seed: u32,
+        shape: f64,
+        min_a: f64,
+        max_b: f64,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(shape, min_a, max_b)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [u32; 5],
+        params: PowerFunctionParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.shape, params.min_a, params.max_b)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("PowerFunction");
+        encoder.write(&params.shape);
+        encoder.write(&params.min_a);
+        encoder.write(&params.max_b);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "PowerFunction")?;
+        let params = PowerFunctionParams {
+            shape: decoder.read()?,
+            min_a: decoder.read()?,
+            max_b: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> PowerFunction<R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
         Self {
-            xyzuv: generate_random_state(_seed),
+            rng,
             shape: 1_f64,
             min_a: 0_f64,
             max_b: 1_f64,
         }
     }
 
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
+    }
+
     /// Computes a random number
     pub fn sample(&mut self) -> f64 {
         // Algorithm 3.67
@@ -41,7 +162,7 @@ impl PowerFunction {
         // Generate a uniform random number U in the interval (0, 1) and calculate Y = U^γ_inv
         // Step 2
         // Calculate X = a + (b - a)Y as the desired random number
-        xorshift160_greater_than_0_and_less_than_1(&mut self.xyzuv).powf(self.shape.powi(-1))
+        uniform_greater_than_0_and_less_than_1(&mut self.rng).powf(self.shape.powi(-1))
             * (self.max_b - self.min_a)
             + self.min_a
     }
@@ -55,11 +176,34 @@ impl PowerFunction {
         shape: f64,
         min_a: f64,
         max_b: f64,
-    ) -> Result<(f64, f64, f64), &str> {
-        if shape <= 0_f64 {
-            Err("The shape parameter γ is less than or equal to 0. The parameters of the probability variable will remain unchanged.")
+    ) -> Result<(f64, f64, f64), ParameterUpdateError> {
+        if !shape.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "shape",
+                value: shape,
+            })
+        } else if shape <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "shape",
+                value: shape,
+            })
+        } else if !min_a.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "min_a",
+                value: min_a,
+            })
+        } else if !max_b.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "max_b",
+                value: max_b,
+            })
         } else if min_a >= max_b {
-            Err("The upper boundary parameter b is equal to or greater than the lower boundary parameter a. The parameters of the probability variable will remain unchanged.")
+            Err(ParameterUpdateError::InvalidOrdering {
+                lower: "min_a",
+                lower_value: min_a,
+                upper: "max_b",
+                upper_value: max_b,
+            })
         } else {
             self.shape = shape;
             self.min_a = min_a;
@@ -67,9 +211,52 @@ impl PowerFunction {
             Ok((shape, min_a, max_b))
         }
     }
+
+    /// Returns the shape parameter γ.
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the lower boundary parameter a.
+    pub fn min_a(&self) -> f64 {
+        self.min_a
+    }
+
+    /// Returns the upper boundary parameter b.
+    pub fn max_b(&self) -> f64 {
+        self.max_b
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> PowerFunctionParams {
+        PowerFunctionParams {
+            shape: self.shape(),
+            min_a: self.min_a(),
+            max_b: self.max_b(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for PowerFunction<R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
+}
+
+impl<R: RandomAccessRng> PowerFunction<R> {
+    /// Returns the random number at `index` of the stream without generating the preceding ones,
+    /// leaving the state of `self` unchanged.
+    /// It equals the result of the `index`-th call (counting from 0) of `sample` from the start of the stream,
+    /// unless an earlier call rejected a uniform random number, which happens with probability about 2^-32 per call.
+    pub fn sample_at(&self, index: u128) -> f64 {
+        let mut distribution = self.clone();
+        distribution.rng.seek(index);
+        distribution.sample()
+    }
 }
 
-impl core::fmt::Display for PowerFunction {
+impl<R: CoreRng> core::fmt::Display for PowerFunction<R> {
     /// Formatter for displaying using println! macro
     /// * Type of the structure
     /// * Scale parameter
@@ -82,3 +269,12 @@ impl core::fmt::Display for PowerFunction {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for PowerFunction<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        PowerFunction::sample(self)
+    }
+}
@@ -1,4 +1,9 @@
-use crate::standard_distributions::{generate_random_state, standard_exponential};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::standard_exponential;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, RandomAccessRng, SeedSequence,
+    SplittableDistribution, SplittableRng, Xorshift160,
+};
 
 /// Rayleigh distribution.
 /// # Examples
@@ -8,44 +13,183 @@ use crate::standard_distributions::{generate_random_state, standard_exponential}
 ///
 /// // To change the parameter of the random variable
 /// let scale: f64 = 1.5f64;
-/// let result: Result<f64, &str> = rayleigh.try_set_params(scale);
+/// let result: Result<f64, rand_simple::ParameterUpdateError> = rayleigh.try_set_params(scale);
 /// assert_eq!(format!("{rayleigh}"), "Rayleigh(Scale parameter) = Rayleigh(1.5)");
 /// ```
-pub struct Rayleigh {
-    xyzuv: [u32; 5], // 状態変数
-    scale: f64,      // 尺度母数
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rayleigh<R = Xorshift160> {
+    rng: R,     // 状態変数
+    scale: f64, // 尺度母数
 }
 
+/// Parameters of the [`Rayleigh`] distribution, returned by [`Rayleigh::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayleighParams {
+    /// Scale parameter
+    pub scale: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl Rayleigh {
     /// Constructor for initializing a new random number generator.
     /// * `_seed` - The seed for the random number generator.
     pub fn new(_seed: u32) -> Self {
         // Create a new instance of the random number generator with the specified seed.
-        Self {
-            xyzuv: generate_random_state(_seed),
-            scale: 1_f64,
-        }
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `scale` - Scale parameter
+    pub fn try_new(seed: u32, scale: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(scale)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [u32; 5],
+        params: RayleighParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.scale)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Rayleigh");
+        encoder.write(&params.scale);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Rayleigh")?;
+        let params = RayleighParams {
+            scale: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Rayleigh<R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
+        Self { rng, scale: 1_f64 }
+    }
+
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
     }
 
     /// Generate a random sample.
     pub fn sample(&mut self) -> f64 {
         // アルゴリズム 3.51
-        (2f64 * standard_exponential(&mut self.xyzuv)).sqrt() * self.scale
+        (2f64 * standard_exponential(&mut self.rng)).sqrt() * self.scale
     }
 
     /// Modify the parameters of the probability variable.
     /// * `scale` - Scale parameter
-    pub fn try_set_params(&mut self, scale: f64) -> Result<f64, &str> {
-        if scale <= 0_f64 {
-            Err("The scale parameter is less than or equal to zero. The parameters of the probability variable will remain unchanged.")
+    pub fn try_set_params(&mut self, scale: f64) -> Result<f64, ParameterUpdateError> {
+        if !scale.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "scale",
+                value: scale,
+            })
+        } else if scale <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "scale",
+                value: scale,
+            })
         } else {
             self.scale = scale;
             Ok(self.scale)
         }
     }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> RayleighParams {
+        RayleighParams {
+            scale: self.scale(),
+        }
+    }
 }
 
-impl core::fmt::Display for Rayleigh {
+impl<R: SplittableRng> SplittableDistribution for Rayleigh<R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
+}
+
+impl<R: RandomAccessRng> Rayleigh<R> {
+    /// Returns the random number at `index` of the stream without generating the preceding ones,
+    /// leaving the state of `self` unchanged.
+    /// It equals the result of the `index`-th call (counting from 0) of `sample` from the start of the stream,
+    /// unless an earlier call rejected a uniform random number, which happens with probability about 2^-32 per call.
+    pub fn sample_at(&self, index: u128) -> f64 {
+        let mut distribution = self.clone();
+        distribution.rng.seek(index);
+        distribution.sample()
+    }
+}
+
+impl<R: CoreRng> core::fmt::Display for Rayleigh<R> {
     /// Implements the Display trait for the Rayleigh struct.
     /// This allows the struct to be formatted and displayed using macros like println!
     /// It displays information about the scale parameter.
@@ -54,3 +198,12 @@ impl core::fmt::Display for Rayleigh {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for Rayleigh<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        Rayleigh::sample(self)
+    }
+}
@@ -1,5 +1,8 @@
-use crate::standard_distributions::{
-    generate_random_state, xorshift160_greater_than_0_and_less_than_1,
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::uniform_greater_than_0_and_less_than_1;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, RandomAccessRng, SeedSequence,
+    SplittableDistribution, SplittableRng, Xorshift160,
 };
 
 /// Reflected Weibull Distribution
@@ -17,35 +20,153 @@ use crate::standard_distributions::{
 /// let shape: f64 = 2f64;     // The shape parameter, controlling the distribution's tail
 /// let location: f64 = 3f64;  // The location parameter, shifting the distribution
 /// let scale: f64 = 1.5f64;   // The scale parameter, stretching or compressing the distribution
-/// let result: Result<(f64, f64, f64), &str> = reflected_weibull.try_set_params(shape, location, scale);
+/// let result: Result<(f64, f64, f64), rand_simple::ParameterUpdateError> = reflected_weibull.try_set_params(shape, location, scale);
 ///
 /// // After updating the parameters, verify the distribution's state
 /// assert_eq!(format!("{reflected_weibull}"), "RWeibull(Shape parameter, Location Parameter, Scale parameter) = RWeibull(2, 3, 1.5)");
 /// ```
-pub struct ReflectedWeibull {
-    xyzuv: [u32; 5], // 状態変数
-    shape: f64,      // 形状母数の逆数
-    location: f64,   // 位置母数
-    scale: f64,      // 尺度母数
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReflectedWeibull<R = Xorshift160> {
+    rng: R,        // 状態変数
+    shape: f64,    // 形状母数の逆数
+    location: f64, // 位置母数
+    scale: f64,    // 尺度母数
 }
 
+/// Parameters of the [`ReflectedWeibull`] distribution, returned by [`ReflectedWeibull::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReflectedWeibullParams {
+    /// Shape parameter
+    pub shape: f64,
+    /// Location parameter
+    pub location: f64,
+    /// Scale parameter
+    pub scale: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl ReflectedWeibull {
     /// Constructor
     /// * `_seed` - Random seed
     pub fn new(_seed: u32) -> Self {
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `shape` - Shape parameter
+    /// * `location` - Location parameter
+    /// * `scale` - Scale parameter
+    pub fn try_new(
+        seed: u32,
+        shape: f64,
+        location: f64,
+        scale: f64,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(shape, location, scale)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [u32; 5],
+        params: ReflectedWeibullParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.shape, params.location, params.scale)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("ReflectedWeibull");
+        encoder.write(&params.shape);
+        encoder.write(&params.location);
+        encoder.write(&params.scale);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "ReflectedWeibull")?;
+        let params = ReflectedWeibullParams {
+            shape: decoder.read()?,
+            location: decoder.read()?,
+            scale: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> ReflectedWeibull<R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
         Self {
-            xyzuv: generate_random_state(_seed),
+            rng,
             shape: 1_f64,
             location: 0_f64,
             scale: 1_f64,
         }
     }
 
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
+    }
+
     /// Computes a random number.
     pub fn sample(&mut self) -> f64 {
         // Algorithm 3.53: Inverse Transform Sampling
         // Step 1: Uniform random number in (0, 1)
-        let u = xorshift160_greater_than_0_and_less_than_1(&mut self.xyzuv);
+        let u = uniform_greater_than_0_and_less_than_1(&mut self.rng);
         // Step 2
         if u < 0.5_f64 {
             // Apply the inverse function for the lower half of the distribution
@@ -65,10 +186,32 @@ impl ReflectedWeibull {
         shape: f64,
         location: f64,
         scale: f64,
-    ) -> Result<(f64, f64, f64), &str> {
-        if shape <= 0_f64 || scale <= 0_f64 {
-            // Returns an error if the shape or scale parameters are less than or equal to 0.
-            Err("Shape or scale parameter is less than or equal to 0. The parameters of the probability variable will remain unchanged.")
+    ) -> Result<(f64, f64, f64), ParameterUpdateError> {
+        if !shape.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "shape",
+                value: shape,
+            })
+        } else if shape <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "shape",
+                value: shape,
+            })
+        } else if !location.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "location",
+                value: location,
+            })
+        } else if !scale.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "scale",
+                value: scale,
+            })
+        } else if scale <= 0_f64 {
+            Err(ParameterUpdateError::NonPositive {
+                name: "scale",
+                value: scale,
+            })
         } else {
             // Updates the parameters and returns the new values.
             self.shape = shape;
@@ -77,9 +220,52 @@ impl ReflectedWeibull {
             Ok((shape, location, scale))
         }
     }
+
+    /// Returns the shape parameter.
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the location parameter.
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> ReflectedWeibullParams {
+        ReflectedWeibullParams {
+            shape: self.shape(),
+            location: self.location(),
+            scale: self.scale(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for ReflectedWeibull<R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
+}
+
+impl<R: RandomAccessRng> ReflectedWeibull<R> {
+    /// Returns the random number at `index` of the stream without generating the preceding ones,
+    /// leaving the state of `self` unchanged.
+    /// It equals the result of the `index`-th call (counting from 0) of `sample` from the start of the stream,
+    /// unless an earlier call rejected a uniform random number, which happens with probability about 2^-32 per call.
+    pub fn sample_at(&self, index: u128) -> f64 {
+        let mut distribution = self.clone();
+        distribution.rng.seek(index);
+        distribution.sample()
+    }
 }
 
-impl std::fmt::Display for ReflectedWeibull {
+impl<R: CoreRng> std::fmt::Display for ReflectedWeibull<R> {
     /// Formatter for displaying using println! macro and similar constructs.
     /// * Type of the struct
     /// * Shape parameter (inverse)
@@ -94,3 +280,12 @@ impl std::fmt::Display for ReflectedWeibull {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for ReflectedWeibull<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        ReflectedWeibull::sample(self)
+    }
+}
@@ -1,5 +1,10 @@
+use crate::serialization::{Decoder, Encoder};
 use crate::standard_distributions::{
-    generate_random_state, standard_cauchy, standard_exponential, standard_gamma, standard_normal,
+    standard_cauchy, standard_exponential, standard_gamma, standard_normal,
+};
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
 };
 
 /// t-distribution (Student's t-distribution)
@@ -15,7 +20,7 @@ use crate::standard_distributions::{
 /// // If you need to modify the parameters of the t-distribution (degree of freedom).
 /// let degree_of_freedom: u64 = 3_u64;
 /// // Set the new degree of freedom and check if the operation was successful.
-/// let result: Result<u64, &str> = t.try_set_params(degree_of_freedom);
+/// let result: Result<u64, rand_simple::ParameterUpdateError> = t.try_set_params(degree_of_freedom);
 /// // Verify that the degree of freedom has been correctly updated to 3.
 /// assert_eq!(format!("{t}"), "T(Degree of Freedom parameter) = T(3)");
 /// // Generate and print a random number with the updated degree of freedom (3).
@@ -24,17 +29,26 @@ use crate::standard_distributions::{
 ///
 /// This example demonstrates how to initialize a t-distribution instance, change the degree of freedom,
 /// and generate random numbers following the t-distribution.
-pub struct TDistribution {
-    xyzuv_n_0: [u32; 5], // 状態変数
-    xyzuv_n_1: [u32; 5], // 状態変数
+#[derive(Debug, Clone, PartialEq)]
+pub struct TDistribution<R = Xorshift160> {
+    rng_n_0: R, // 状態変数
+    rng_n_1: R, // 状態変数
 
-    xyzuv_u_gamma: [u32; 5],   // 状態変数
-    xyzuv_n_0_gamma: [u32; 5], // 状態変数
-    xyzuv_n_1_gamma: [u32; 5], // 状態変数
+    rng_u_gamma: R,   // 状態変数
+    rng_n_0_gamma: R, // 状態変数
+    rng_n_1_gamma: R, // 状態変数
 
     degree_of_freedom: u64, // 自由度 r ∈ N
 }
 
+/// Parameters of the [`TDistribution`] distribution, returned by [`TDistribution::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TDistributionParams {
+    /// Degree of freedom, must be a natural number (r ≥ 1)
+    pub degree_of_freedom: u64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl TDistribution {
     /// Constructor for the `TDistribution` struct.
     /// This method initializes a new instance of the t-distribution with the provided random seeds.
@@ -53,18 +67,123 @@ impl TDistribution {
     pub fn new(seeds: [u32; 5_usize]) -> Self {
         let adjusted_seeds = crate::adjust_seeds!(seeds);
 
+        Self::from_rngs(adjusted_seeds.map(Xorshift160::new))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seeds` - Random seeds. Adjusted within the constructor to ensure uniqueness
+    /// * `degree_of_freedom` - Degree of freedom, must be a natural number (r ≥ 1)
+    pub fn try_new(
+        seeds: [u32; 5_usize],
+        degree_of_freedom: u64,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seeds);
+        distribution.try_set_params(degree_of_freedom)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a single 64 bit seed via [`SeedSequence`].
+    /// Unlike `new`, no array of distinct seeds is needed: the streams are derived from one master seed with SplitMix64.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal Xorshift160 stream from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [[u32; 5]; 5] {
+        [
+            self.rng_n_0.state(),
+            self.rng_n_1.state(),
+            self.rng_u_gamma.state(),
+            self.rng_n_0_gamma.state(),
+            self.rng_n_1_gamma.state(),
+        ]
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables of Xorshift160, in the same order as the seeds passed to `new`; none may be all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [[u32; 5]; 5],
+        params: TDistributionParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rngs(Xorshift160::from_states(state)?);
+        distribution.try_set_params(params.degree_of_freedom)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("TDistribution");
+        encoder.write(&params.degree_of_freedom);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "TDistribution")?;
+        let params = TDistributionParams {
+            degree_of_freedom: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> TDistribution<R> {
+    /// Constructor from core random number generators (engines), one per internal stream.
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rngs` - Engines of the internal streams, in the same order as the seeds passed to `new`
+    pub fn from_rngs(rngs: [R; 5]) -> Self {
+        let [rng_n_0, rng_n_1, rng_u_gamma, rng_n_0_gamma, rng_n_1_gamma] = rngs;
         Self {
-            xyzuv_n_0: generate_random_state(adjusted_seeds[0]),
-            xyzuv_n_1: generate_random_state(adjusted_seeds[1]),
+            rng_n_0,
+            rng_n_1,
 
-            xyzuv_u_gamma: generate_random_state(adjusted_seeds[2]),
-            xyzuv_n_0_gamma: generate_random_state(adjusted_seeds[3]),
-            xyzuv_n_1_gamma: generate_random_state(adjusted_seeds[4]),
+            rng_u_gamma,
+            rng_n_0_gamma,
+            rng_n_1_gamma,
 
             degree_of_freedom: 1_u64,
         }
     }
 
+    /// Constructor that derives every internal stream of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives every internal stream of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rngs(std::array::from_fn(|_| R::from_seed_sequence(seeds)))
+    }
+
     /// Generates a random sample from the t-distribution based on the current degree of freedom (DoF).
     ///
     /// # Algorithm Description:
@@ -82,15 +201,15 @@ impl TDistribution {
     ///
     pub fn sample(&mut self) -> f64 {
         match self.degree_of_freedom as usize {
-            1_usize => standard_cauchy(&mut self.xyzuv_u_gamma),
+            1_usize => standard_cauchy(&mut self.rng_u_gamma),
             2_usize => {
                 // アルゴリズム 3.84 r: = 2
                 // step 1
                 let mut z = 0_f64;
                 let mut w = 0_f64;
                 while w == 0_f64 {
-                    z = standard_normal(&mut self.xyzuv_n_0, &mut self.xyzuv_n_1);
-                    w = standard_exponential(&mut self.xyzuv_u_gamma);
+                    z = standard_normal(&mut self.rng_n_0, &mut self.rng_n_1);
+                    w = standard_exponential(&mut self.rng_u_gamma);
                 }
                 // step 2
                 z / w.sqrt()
@@ -98,11 +217,11 @@ impl TDistribution {
             _ => {
                 // アルゴリズム 3.85: r > 2
                 // step 1
-                let z = standard_normal(&mut self.xyzuv_n_0, &mut self.xyzuv_n_1);
+                let z = standard_normal(&mut self.rng_n_0, &mut self.rng_n_1);
                 let w = standard_gamma(
-                    &mut self.xyzuv_u_gamma,
-                    &mut self.xyzuv_n_0_gamma,
-                    &mut self.xyzuv_n_1_gamma,
+                    &mut self.rng_u_gamma,
+                    &mut self.rng_n_0_gamma,
+                    &mut self.rng_n_1_gamma,
                     &(self.degree_of_freedom as f64 / 2_f64),
                 );
                 // step 2
@@ -118,7 +237,7 @@ impl TDistribution {
     ///
     /// # Returns:
     /// * `Ok(u64)` - If the input `degree_of_freedom` is valid, returns the updated value.
-    /// * `Err(&str)` - If the input `degree_of_freedom` is invalid (r < 1), returns an error message.
+    /// * `Err(ParameterUpdateError)` - If the input `degree_of_freedom` is invalid (r < 1), returns an error message.
     ///
     /// # Description:
     /// This method is responsible for setting the degree of freedom (DoF) for the random variable.
@@ -132,20 +251,48 @@ impl TDistribution {
     /// let result = dist.try_set_params(3_u64);
     /// assert_eq!(result.unwrap(), 3_u64); // Successfully updates the degree of freedom.
     /// ```
-    pub fn try_set_params(&mut self, degree_of_freedom: u64) -> Result<u64, &str> {
-        // Check if the provided degree_of_freedom is a valid natural number (r ≥ 1).
+    pub fn try_set_params(&mut self, degree_of_freedom: u64) -> Result<u64, ParameterUpdateError> {
         if degree_of_freedom < 1_u64 {
-            // Return an error if the input is invalid, without changing the current parameter.
-            Err("The degree of freedom must be a natural number. The parameter remains unchanged.")
+            Err(ParameterUpdateError::NonPositive {
+                name: "degree_of_freedom",
+                value: degree_of_freedom as f64,
+            })
         } else {
             // Update the degree of freedom with the valid input and return the updated value.
             self.degree_of_freedom = degree_of_freedom;
             Ok(degree_of_freedom)
         }
     }
+
+    /// Returns the degree of freedom (r).
+    pub fn degree_of_freedom(&self) -> u64 {
+        self.degree_of_freedom
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> TDistributionParams {
+        TDistributionParams {
+            degree_of_freedom: self.degree_of_freedom(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for TDistribution<R> {
+    /// Moves every internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        for rng in [
+            &mut self.rng_n_0,
+            &mut self.rng_n_1,
+            &mut self.rng_u_gamma,
+            &mut self.rng_n_0_gamma,
+            &mut self.rng_n_1_gamma,
+        ] {
+            rng.jump_substream();
+        }
+    }
 }
 
-impl std::fmt::Display for TDistribution {
+impl<R: CoreRng> std::fmt::Display for TDistribution<R> {
     /// Formatter implementation for the TDistribution struct.
     /// This allows the `println!` macro or other formatting macros
     /// to display the t-distribution in a human-readable format.
@@ -167,3 +314,12 @@ impl std::fmt::Display for TDistribution {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for TDistribution<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        TDistribution::sample(self)
+    }
+}
@@ -1,4 +1,9 @@
-use crate::standard_distributions::{generate_random_state, xorshift160_0_to_1};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::uniform_0_to_1;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
 
 /// Represents a triangular distribution defined by a minimum value, maximum value, and mode.
 ///
@@ -17,7 +22,7 @@ use crate::standard_distributions::{generate_random_state, xorshift160_0_to_1};
 /// let min: f64 = -1_f64;
 /// let max: f64 = 1_f64;
 /// let mode: f64 = 0.25_f64;
-/// let result: Result<(f64, f64, f64), &str> = triangular.try_set_params(min, max, mode);
+/// let result: Result<(f64, f64, f64), rand_simple::ParameterUpdateError> = triangular.try_set_params(min, max, mode);
 ///
 /// assert_eq!(format!("{triangular}"), "TRI(Min, Max, Mode) = TRI(-1, 1, 0.25)");
 /// println!(
@@ -27,17 +32,30 @@ use crate::standard_distributions::{generate_random_state, xorshift160_0_to_1};
 /// ```
 ///
 /// # Fields
-/// - `xyzuv`: A state variable used for random number generation.
+/// - `rng`: A state variable used for random number generation.
 /// - `min`: The minimum value of the distribution, defining the left endpoint of the interval.
 /// - `max`: The maximum value of the distribution, defining the right endpoint of the interval.
 /// - `mode`: The mode (peak) of the distribution, indicating the value where the distribution reaches its maximum density.
-pub struct Triangular {
-    xyzuv: [u32; 5], // 状態変数
-    min: f64,        // 最小値
-    max: f64,        // 最大値
-    mode: f64,       // モード
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triangular<R = Xorshift160> {
+    rng: R,    // 状態変数
+    min: f64,  // 最小値
+    max: f64,  // 最大値
+    mode: f64, // モード
 }
 
+/// Parameters of the [`Triangular`] distribution, returned by [`Triangular::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangularParams {
+    /// The minimum value of the distribution
+    pub min: f64,
+    /// The maximum value of the distribution
+    pub max: f64,
+    /// The mode (most probable value) of the distribution
+    pub mode: f64,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl Triangular {
     /// Constructor for the `Triangular` struct.
     /// Initializes the random number generator state and sets default parameters.
@@ -50,14 +68,119 @@ impl Triangular {
     /// - `max`: 1.0 (upper bound of the interval)
     /// - `mode`: 0.5 (most likely value within the interval)
     pub fn new(_seed: u32) -> Self {
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `min` - The minimum value of the distribution
+    /// * `max` - The maximum value of the distribution
+    /// * `mode` - The mode (most probable value) of the distribution
+    /// # Example
+    /// ```
+    /// let triangular = rand_simple::Triangular::try_new(1192_u32, -1_f64, 1_f64, 0.25_f64).unwrap();
+    /// assert_eq!(format!("{triangular}"), "TRI(Min, Max, Mode) = TRI(-1, 1, 0.25)");
+    /// ```
+    pub fn try_new(seed: u32, min: f64, max: f64, mode: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(min, max, mode)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [u32; 5],
+        params: TriangularParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.min, params.max, params.mode)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Triangular");
+        encoder.write(&params.min);
+        encoder.write(&params.max);
+        encoder.write(&params.mode);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Triangular")?;
+        let params = TriangularParams {
+            min: decoder.read()?,
+            max: decoder.read()?,
+            mode: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Triangular<R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
         Self {
-            xyzuv: generate_random_state(_seed),
+            rng,
             min: 0_f64,
             max: 1_f64,
             mode: 0.5_f64,
         }
     }
 
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
+    }
+
     /// Generates a random number following the triangular distribution.
     ///
     /// This method uses Algorithm 3.95 (Inverse Transform Sampling) to generate a random value
@@ -71,7 +194,7 @@ impl Triangular {
         let d_1m = 1_f64 - d;
 
         // step 1
-        let u = xorshift160_0_to_1(&mut self.xyzuv);
+        let u = uniform_0_to_1(&mut self.rng);
 
         // step 2
         let y = if u < d {
@@ -97,17 +220,42 @@ impl Triangular {
     ///
     /// # Returns
     /// - `Ok((min, max, mode))` if the parameters are valid.
-    /// - `Err(&str)` if the parameters are invalid, with an error message explaining the issue.
+    /// - `Err(ParameterUpdateError)` if the parameters are invalid, with an error message explaining the issue.
     pub fn try_set_params(
         &mut self,
         min: f64,
         max: f64,
         mode: f64,
-    ) -> Result<(f64, f64, f64), &str> {
-        if min >= max {
-            Err("最小値と最大値が等しい、あるいは最小値の方が大きいです。確率変数のパラメータは前回の設定を維持します。")
+    ) -> Result<(f64, f64, f64), ParameterUpdateError> {
+        if !min.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "min",
+                value: min,
+            })
+        } else if !max.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "max",
+                value: max,
+            })
+        } else if !mode.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "mode",
+                value: mode,
+            })
+        } else if min >= max {
+            Err(ParameterUpdateError::InvalidOrdering {
+                lower: "min",
+                lower_value: min,
+                upper: "max",
+                upper_value: max,
+            })
         } else if mode < min || max < mode {
-            Err("モードが最小値よりも小さい、あるいはモードが最大値よりも大きいです。確率変数のパラメータは前回の設定を維持します。")
+            Err(ParameterUpdateError::OutOfRange {
+                name: "mode",
+                value: mode,
+                min,
+                max,
+            })
         } else {
             self.min = min;
             self.max = max;
@@ -115,9 +263,40 @@ impl Triangular {
             Ok((self.min, self.max, self.mode))
         }
     }
+
+    /// Returns the minimum value.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Returns the maximum value.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Returns the mode (most probable value).
+    pub fn mode(&self) -> f64 {
+        self.mode
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> TriangularParams {
+        TriangularParams {
+            min: self.min(),
+            max: self.max(),
+            mode: self.mode(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for Triangular<R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
 }
 
-impl core::fmt::Display for Triangular {
+impl<R: CoreRng> core::fmt::Display for Triangular<R> {
     /// Formatter for displaying with println! macro and others.
     /// * Range (Closed Interval)
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -129,3 +308,12 @@ impl core::fmt::Display for Triangular {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for Triangular<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        Triangular::sample(self)
+    }
+}
@@ -1,6 +1,14 @@
-use crate::standard_distributions::{generate_random_state, xorshift160_0_to_1};
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::uniform_0_to_1;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, RandomAccessRng, SeedSequence,
+    SplittableDistribution, SplittableRng, Xorshift160,
+};
 
 /// Uniform distribution
+///
+/// On engines with [`crate::CoreRng::HIGH_PRECISION`], such as [`crate::Mt19937`], the uniform random number
+/// is the 53 bit value of `genrand_res53` in [0, 1), so the maximum itself is not drawn.
 /// # Example
 /// ```
 /// let mut uniform = rand_simple::Uniform::new(1192_u32);
@@ -10,49 +18,212 @@ use crate::standard_distributions::{generate_random_state, xorshift160_0_to_1};
 /// // When changing the parameters of the random variable
 /// let min: f64 = -1_f64;
 /// let max: f64 = 1_f64;
-/// let result: Result<(f64, f64), &str> = uniform.try_set_params(min, max);
+/// let result: Result<(f64, f64), rand_simple::ParameterUpdateError> = uniform.try_set_params(min, max);
 /// assert_eq!(format!("{uniform}"), "Range (Closed Interval): [-1, 1]");
 /// println!("Returns a random number -> {}", uniform.sample());
 /// ```
-pub struct Uniform {
-    xyzuv: [u32; 5], // 状態変数
-    min: f64,        // 最小値
-    max: f64,        // 最大値
+#[derive(Debug, Clone, PartialEq)]
+pub struct Uniform<R = Xorshift160> {
+    rng: R,   // 状態変数
+    min: f64, // 最小値
+    max: f64, // 最大値
+}
+
+/// Parameters of the [`Uniform`] distribution, returned by [`Uniform::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniformParams {
+    /// Minimum value
+    pub min: f64,
+    /// Maximum value
+    pub max: f64,
 }
 
+// Xorshift160 の状態変数を使用する生成・保存処理
 impl Uniform {
     /// Constructor
     /// * `_seed` - The seed for the random number generator
     pub fn new(_seed: u32) -> Self {
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `min` - Minimum value
+    /// * `max` - Maximum value
+    pub fn try_new(seed: u32, min: f64, max: f64) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(min, max)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [u32; 5],
+        params: UniformParams,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.min, params.max)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new("Uniform");
+        encoder.write(&params.min);
+        encoder.write(&params.max);
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, "Uniform")?;
+        let params = UniformParams {
+            min: decoder.read()?,
+            max: decoder.read()?,
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<R: CoreRng> Uniform<R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
         Self {
-            xyzuv: generate_random_state(_seed),
+            rng,
             min: 0_f64,
             max: 1_f64,
         }
     }
 
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
+    }
+
     /// Calculate a random number.
     /// # Returns
     /// A random floating-point number within the specified range.
     pub fn sample(&mut self) -> f64 {
-        xorshift160_0_to_1(&mut self.xyzuv) * (self.max - self.min) + self.min
+        uniform_0_to_1(&mut self.rng) * (self.max - self.min) + self.min
     }
 
     /// Attempt to modify the parameters of the random variable.
     /// * `min` - Minimum value
     /// * `max` - Maximum value
-    pub fn try_set_params(&mut self, min: f64, max: f64) -> Result<(f64, f64), &'static str> {
-        if min >= max {
-            Err("The minimum and maximum values are equal or the minimum value is greater. The parameters of the random variable will remain unchanged.")
+    pub fn try_set_params(
+        &mut self,
+        min: f64,
+        max: f64,
+    ) -> Result<(f64, f64), ParameterUpdateError> {
+        if !min.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "min",
+                value: min,
+            })
+        } else if !max.is_finite() {
+            Err(ParameterUpdateError::NotFinite {
+                name: "max",
+                value: max,
+            })
+        } else if min >= max {
+            Err(ParameterUpdateError::InvalidOrdering {
+                lower: "min",
+                lower_value: min,
+                upper: "max",
+                upper_value: max,
+            })
         } else {
             self.min = min;
             self.max = max;
             Ok((self.min, self.max))
         }
     }
+
+    /// Returns the minimum value.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Returns the maximum value.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> UniformParams {
+        UniformParams {
+            min: self.min(),
+            max: self.max(),
+        }
+    }
+}
+
+impl<R: SplittableRng> SplittableDistribution for Uniform<R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
 }
 
-impl core::fmt::Display for Uniform {
+impl<R: RandomAccessRng> Uniform<R> {
+    /// Returns the random number at `index` of the stream without generating the preceding ones,
+    /// leaving the state of `self` unchanged.
+    /// It equals the result of the `index`-th call (counting from 0) of `sample` from the start of the stream.
+    pub fn sample_at(&self, index: u128) -> f64 {
+        let mut distribution = self.clone();
+        distribution.rng.seek(index);
+        distribution.sample()
+    }
+}
+
+impl<R: CoreRng> core::fmt::Display for Uniform<R> {
     /// Formatter for displaying with println! macro and others.
     /// * Range (Closed Interval)
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -60,3 +231,12 @@ impl core::fmt::Display for Uniform {
         Ok(())
     }
 }
+
+impl<R: CoreRng> crate::Distribution for Uniform<R> {
+    type Output = f64;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> f64 {
+        Uniform::sample(self)
+    }
+}
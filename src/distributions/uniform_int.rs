@@ -0,0 +1,345 @@
+use crate::serialization::{Decoder, Encoder};
+use crate::standard_distributions::uniform_integer_0_to;
+use crate::{
+    CoreRng, DeserializeError, ParameterUpdateError, SeedSequence, SplittableDistribution,
+    SplittableRng, Xorshift160,
+};
+
+/// Integer types that [`UniformInt`] can generate: `u32`, `u64`, `i64` and `usize`.
+pub trait UniformIntType: Copy + PartialOrd + core::fmt::Debug + core::fmt::Display {
+    /// Smallest value of the type, used as the default minimum.
+    const MIN: Self;
+    /// Largest value of the type, used as the default maximum.
+    const MAX: Self;
+    /// Name of the distribution in the byte format of [`UniformInt::to_bytes`].
+    const NAME: &'static str;
+
+    /// Maps the value to `u64`, preserving the order and the differences between values.
+    fn to_offset(self) -> u64;
+
+    /// Inverse of [`Self::to_offset`].
+    fn from_offset(offset: u64) -> Self;
+
+    /// Converts the value to `f64` for [`ParameterUpdateError`], rounding large values.
+    fn to_f64(self) -> f64;
+}
+
+// 符号なし整数はそのまま u64 に変換する
+macro_rules! impl_uniform_int_type_unsigned {
+    ($t:ty, $name:literal) => {
+        impl UniformIntType for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+            const NAME: &'static str = $name;
+
+            fn to_offset(self) -> u64 {
+                self as u64
+            }
+
+            fn from_offset(offset: u64) -> Self {
+                offset as $t
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    };
+}
+impl_uniform_int_type_unsigned!(u32, "UniformInt<u32>");
+impl_uniform_int_type_unsigned!(u64, "UniformInt<u64>");
+impl_uniform_int_type_unsigned!(usize, "UniformInt<usize>");
+
+// 符号付き整数は符号ビットを反転して、大小関係を保ったまま u64 に変換する
+impl UniformIntType for i64 {
+    const MIN: Self = i64::MIN;
+    const MAX: Self = i64::MAX;
+    const NAME: &'static str = "UniformInt<i64>";
+
+    fn to_offset(self) -> u64 {
+        (self as u64) ^ (1_u64 << 63)
+    }
+
+    fn from_offset(offset: u64) -> Self {
+        (offset ^ (1_u64 << 63)) as i64
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+/// Discrete uniform distribution over the integers of a closed interval `[min, max]`
+///
+/// Uses Lemire's multiply-shift method with rejection, so every integer of the interval
+/// is equally likely, without the modulo bias of `(u * n) as u64` or `x % n`.
+/// # Example
+/// ```
+/// // A six-sided die
+/// let mut die = rand_simple::UniformInt::<u32>::try_new(1192_u32, 1_u32, 6_u32).unwrap();
+/// assert_eq!(format!("{die}"), "Range (Closed Interval): [1, 6]");
+/// let face: u32 = die.sample();
+/// assert!((1_u32..=6_u32).contains(&face));
+///
+/// // When changing the parameters of the random variable
+/// let mut offset = rand_simple::UniformInt::<i64>::new(1192_u32);
+/// assert_eq!(format!("{offset}"), format!("Range (Closed Interval): [{}, {}]", i64::MIN, i64::MAX));
+/// let result: Result<(i64, i64), rand_simple::ParameterUpdateError> = offset.try_set_params(-10_i64, 10_i64);
+/// assert_eq!(format!("{offset}"), "Range (Closed Interval): [-10, 10]");
+/// println!("Returns a random number -> {}", offset.sample());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniformInt<T, R = Xorshift160> {
+    rng: R, // 状態変数
+    min: T, // 最小値
+    max: T, // 最大値
+}
+
+/// Parameters of the [`UniformInt`] distribution, returned by [`UniformInt::params`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniformIntParams<T> {
+    /// Minimum value
+    pub min: T,
+    /// Maximum value
+    pub max: T,
+}
+
+// Xorshift160 の状態変数を使用する生成・保存処理
+impl<T: UniformIntType> UniformInt<T> {
+    /// Constructor. The range is set to every value of `T`, `[T::MIN, T::MAX]`.
+    /// * `_seed` - The seed for the random number generator
+    pub fn new(_seed: u32) -> Self {
+        Self::from_rng(Xorshift160::new(_seed))
+    }
+
+    /// Constructor that also sets the parameters of the random variable.
+    /// Equivalent to `new` followed by `try_set_params`, so an instance with invalid parameters is never returned.
+    /// * `seed` - Random seed
+    /// * `min` - Minimum value
+    /// * `max` - Maximum value
+    pub fn try_new(seed: u32, min: T, max: T) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::new(seed);
+        distribution.try_set_params(min, max)?;
+        Ok(distribution)
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the internal state of Xorshift160 from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng_seed_sequence(seeds)
+    }
+
+    /// Constructor seeded with entropy from the operating system (see [`crate::fill_entropy`]).
+    /// The parameters are set to the same defaults as `new`, and the sequence is not reproducible.
+    pub fn from_entropy() -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_entropy())
+    }
+
+    /// Returns a snapshot of the internal state of the random number generator.
+    /// Together with [`Self::params`], it can be passed to [`Self::from_state`] to resume the sequence.
+    pub fn state(&self) -> [u32; 5] {
+        self.rng.state()
+    }
+
+    /// Restores a generator from a snapshot taken with [`Self::state`] and [`Self::params`].
+    /// The restored generator continues the exact same sequence as the original one.
+    /// * `state` - State variables (x, y, z, u, v) of Xorshift160, not all 0
+    /// * `params` - Parameters of the random variable
+    pub fn from_state(
+        state: [u32; 5],
+        params: UniformIntParams<T>,
+    ) -> Result<Self, ParameterUpdateError> {
+        let mut distribution = Self::from_rng(Xorshift160::from_state(state)?);
+        distribution.try_set_params(params.min, params.max)?;
+        Ok(distribution)
+    }
+
+    /// Serializes the parameters and the state of the random number generator
+    /// into a versioned byte format tagged with the distribution name and the integer type.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = self.params();
+        let mut encoder = Encoder::new(T::NAME);
+        encoder.write(&params.min.to_offset());
+        encoder.write(&params.max.to_offset());
+        encoder.write(&self.state());
+        encoder.finish()
+    }
+
+    /// Restores a generator from bytes written by [`Self::to_bytes`].
+    /// Fails if the bytes are broken, or hold another distribution or integer type.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes, T::NAME)?;
+        let params = UniformIntParams {
+            min: T::from_offset(decoder.read()?),
+            max: T::from_offset(decoder.read()?),
+        };
+        let state = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self::from_state(state, params)?)
+    }
+}
+
+impl<T: UniformIntType, R: CoreRng> UniformInt<T, R> {
+    /// Constructor from a core random number generator (engine).
+    /// The parameters are set to the same defaults as `new`.
+    /// * `rng` - Engine of the random number generator
+    pub fn from_rng(rng: R) -> Self {
+        Self {
+            rng,
+            min: T::MIN,
+            max: T::MAX,
+        }
+    }
+
+    /// Constructor that derives the engine of type `R` from a single 64 bit seed via [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seed` - Master seed; the same seed always yields the same sequence
+    pub fn from_rng_seed(seed: u64) -> Self {
+        Self::from_rng_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor that derives the engine of type `R` from a [`SeedSequence`].
+    /// The parameters are set to the same defaults as `new`.
+    /// * `seeds` - Seed sequence; consecutive calls yield independent generators
+    pub fn from_rng_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::from_rng(R::from_seed_sequence(seeds))
+    }
+
+    /// Calculate a random number.
+    /// # Returns
+    /// A random integer within the specified closed interval, each value with equal probability.
+    pub fn sample(&mut self) -> T {
+        let min: u64 = self.min.to_offset();
+        let range: u64 = self.max.to_offset() - min;
+        T::from_offset(min + uniform_integer_0_to(&mut self.rng, range))
+    }
+
+    /// Attempt to modify the parameters of the random variable.
+    /// * `min` - Minimum value
+    /// * `max` - Maximum value
+    pub fn try_set_params(&mut self, min: T, max: T) -> Result<(T, T), ParameterUpdateError> {
+        if min >= max {
+            Err(ParameterUpdateError::InvalidOrdering {
+                lower: "min",
+                lower_value: min.to_f64(),
+                upper: "max",
+                upper_value: max.to_f64(),
+            })
+        } else {
+            self.min = min;
+            self.max = max;
+            Ok((self.min, self.max))
+        }
+    }
+
+    /// Returns the minimum value.
+    pub fn min(&self) -> T {
+        self.min
+    }
+
+    /// Returns the maximum value.
+    pub fn max(&self) -> T {
+        self.max
+    }
+
+    /// Returns the current parameters of the random variable.
+    pub fn params(&self) -> UniformIntParams<T> {
+        UniformIntParams {
+            min: self.min(),
+            max: self.max(),
+        }
+    }
+}
+
+impl<T: UniformIntType, R: SplittableRng> SplittableDistribution for UniformInt<T, R> {
+    /// Moves the internal engine to its next substream.
+    fn jump_substreams(&mut self) {
+        self.rng.jump_substream();
+    }
+}
+
+impl<T: UniformIntType, R: CoreRng> core::fmt::Display for UniformInt<T, R> {
+    /// Formatter for displaying with println! macro and others.
+    /// * Range (Closed Interval)
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Range (Closed Interval): [{}, {}]", self.min, self.max)?;
+        Ok(())
+    }
+}
+
+impl<T: UniformIntType, R: CoreRng> crate::Distribution for UniformInt<T, R> {
+    type Output = T;
+
+    /// Generate a random number. Delegates to the inherent `sample` method.
+    fn sample(&mut self) -> T {
+        UniformInt::sample(self)
+    }
+}
+
+/// 符号付き整数の区間で、各値がほぼ同じ回数だけ出て、全範囲では負の値も出ることを確認するテスト
+#[test]
+fn test_uniform_int_signed() {
+    let mut uniform_int = UniformInt::<i64>::try_new(1192_u32, -3_i64, 2_i64).unwrap();
+    let mut counts: [usize; 6] = [0_usize; 6];
+    for _ in 0..60_000 {
+        let x: i64 = uniform_int.sample();
+        assert!((-3_i64..=2_i64).contains(&x));
+        counts[(x + 3_i64) as usize] += 1_usize;
+    }
+    assert!(counts
+        .iter()
+        .all(|count| count.abs_diff(10_000_usize) < 500_usize));
+
+    let mut uniform_int = UniformInt::<i64>::new(1192_u32);
+    assert_eq!((uniform_int.min(), uniform_int.max()), (i64::MIN, i64::MAX));
+    let negatives: usize = (0..1_000).filter(|_| uniform_int.sample() < 0_i64).count();
+    assert!((400_usize..600_usize).contains(&negatives));
+}
+
+/// min >= max の母数を拒否し、区間を変更しないことを確認するテスト
+#[test]
+fn test_uniform_int_invalid_params() {
+    let mut uniform_int = UniformInt::<i64>::try_new(1192_u32, -3_i64, 2_i64).unwrap();
+    assert_eq!(
+        uniform_int.try_set_params(5_i64, 5_i64),
+        Err(ParameterUpdateError::InvalidOrdering {
+            lower: "min",
+            lower_value: 5_f64,
+            upper: "max",
+            upper_value: 5_f64,
+        })
+    );
+    assert_eq!(
+        uniform_int.try_set_params(2_i64, -3_i64),
+        Err(ParameterUpdateError::InvalidOrdering {
+            lower: "min",
+            lower_value: 2_f64,
+            upper: "max",
+            upper_value: -3_f64,
+        })
+    );
+    assert_eq!((uniform_int.min(), uniform_int.max()), (-3_i64, 2_i64));
+    assert!(UniformInt::<u32>::try_new(1192_u32, 6_u32, 1_u32).is_err());
+}
+
+/// u32 の区間が表示の書式と一致し、区間内の値だけを生成することを確認するテスト
+#[test]
+fn test_uniform_int_u32() {
+    let mut die = UniformInt::<u32>::try_new(1192_u32, 1_u32, 6_u32).unwrap();
+    assert_eq!(format!("{die}"), "Range (Closed Interval): [1, 6]");
+    assert!((0..1_000).all(|_| (1_u32..=6_u32).contains(&die.sample())));
+
+    let full = UniformInt::<u32>::new(1192_u32);
+    assert_eq!(
+        format!("{full}"),
+        "Range (Closed Interval): [0, 4294967295]"
+    );
+}
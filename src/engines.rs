@@ -0,0 +1,8 @@
+// 子モジュールに、各疑似乱数生成器 (エンジン) の詳細を記述する
+pub mod chacha20; // ChaCha20 (暗号論的擬似乱数生成器)
+pub mod high_precision; // 53 bit 精度の一様乱数を使用するアダプター
+pub mod mt19937; // MT19937 と MT19937-64 (メルセンヌ・ツイスター)
+pub mod pcg; // PCG32 と PCG64
+pub mod philox; // Philox4x32-10 (カウンター方式)
+pub mod xorshift160; // Xorshift160 (既定のエンジン)
+pub mod xoshiro256; // xoshiro256** と xoshiro256+
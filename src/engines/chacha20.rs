@@ -0,0 +1,269 @@
+use crate::{fill_entropy, CoreRng, RandomAccessRng, SeedSequence, SplittableRng};
+
+/// ChaCha の定数 "expand 32-byte k"
+const CONSTANTS_CHACHA: [u32; 4] = [0x6170_7865, 0x3320_646E, 0x7962_2D32, 0x6B20_6574];
+
+/// ChaCha20 のダブルラウンド数 (20 ラウンド)
+const DOUBLE_ROUNDS_CHACHA20: usize = 10_usize;
+
+/// 1 ブロックの出力の語数
+const BLOCK_WORDS_CHACHA: u128 = 16_u128;
+
+/// 1 つのストリームの語数 (64 bit のブロックカウンター × 16 語 = 2^68 語)
+const STREAM_WORDS_CHACHA: u128 = 1_u128 << 68;
+
+/// ChaCha の quarter round
+#[inline]
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(16);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(12);
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(8);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(7);
+}
+
+/// ChaCha20 のブロック関数 (RFC 8439, 2.3 節)
+/// # 引数
+/// * `key` - 256 bit の鍵
+/// * `input` - 状態の 12〜15 語目 (RFC 8439 ではカウンター 32 bit と nonce 96 bit)
+fn chacha20_block(key: &[u32; 8], input: &[u32; 4]) -> [u32; 16] {
+    let mut state: [u32; 16] = [0_u32; 16];
+    state[..4].copy_from_slice(&CONSTANTS_CHACHA);
+    state[4..12].copy_from_slice(key);
+    state[12..].copy_from_slice(input);
+    let mut x: [u32; 16] = state;
+    for _ in 0..DOUBLE_ROUNDS_CHACHA20 {
+        // 列のラウンド
+        quarter_round(&mut x, 0, 4, 8, 12);
+        quarter_round(&mut x, 1, 5, 9, 13);
+        quarter_round(&mut x, 2, 6, 10, 14);
+        quarter_round(&mut x, 3, 7, 11, 15);
+        // 対角のラウンド
+        quarter_round(&mut x, 0, 5, 10, 15);
+        quarter_round(&mut x, 1, 6, 11, 12);
+        quarter_round(&mut x, 2, 7, 8, 13);
+        quarter_round(&mut x, 3, 4, 9, 14);
+    }
+    for (x_i, s_i) in x.iter_mut().zip(state.iter()) {
+        *x_i = x_i.wrapping_add(*s_i);
+    }
+    x
+}
+
+/// ChaCha20 cryptographically secure engine (Bernstein, 2008; RFC 8439)
+///
+/// The output is the ChaCha20 key stream for a 256 bit key, with a 64 bit block counter and a
+/// 64 bit stream number in place of the nonce. Each stream therefore has 2^68 words, and positions
+/// wrap around modulo 2^68. Unlike Xorshift160, whose state is revealed by five
+/// outputs, past and future outputs cannot be predicted without the key, which makes it suitable
+/// for tokens and security-sensitive shuffles.
+///
+/// Seed it with [`Self::from_entropy`] or a secret [`Self::from_key`] when unpredictability is required.
+/// [`CoreRng::from_seed_sequence`] is provided for reproducible runs, but a [`SeedSequence`] holds
+/// only 64 bits, so keys derived from it must not be relied on for secrecy.
+///
+/// # Example
+/// ```
+/// use rand_simple::{ChaCha20, CoreRng, Uniform};
+///
+/// // Unpredictable 128 bit token
+/// let mut rng = ChaCha20::from_entropy();
+/// let mut token = [0_u8; 16];
+/// rng.fill_bytes(&mut token);
+/// println!("Token -> {token:02x?}");
+///
+/// // Security-sensitive draws from any distribution
+/// let mut uniform = Uniform::from_rng(ChaCha20::from_entropy());
+/// println!("Returns a random number -> {}", uniform.sample());
+/// ```
+/// # References
+/// * Bernstein, D. J. (2008). ChaCha, a variant of Salsa20. Workshop Record of SASC 2008.
+/// * Nir, Y., & Langley, A. (2018). ChaCha20 and Poly1305 for IETF Protocols. RFC 8439.
+/// * URL: https://www.rfc-editor.org/rfc/rfc8439
+#[derive(Clone, PartialEq, Eq)]
+pub struct ChaCha20 {
+    key: [u32; 8],     // 鍵
+    stream: u64,       // ストリーム番号 (nonce)
+    position: u128,    // 次に返す語の位置
+    buffer: [u32; 16], // 位置 position を含むブロックの出力 (position が 16 の倍数のときは未使用)
+}
+
+impl ChaCha20 {
+    /// Constructor from a 256 bit key, positioned at the start of stream 0.
+    /// * `key` - Key, read as eight little-endian 32 bit words as in RFC 8439
+    pub fn from_key(key: [u8; 32]) -> Self {
+        let mut words: [u32; 8] = [0_u32; 8];
+        for (word, bytes) in words.iter_mut().zip(key.chunks_exact(4_usize)) {
+            *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        Self {
+            key: words,
+            stream: 0_u64,
+            position: 0_u128,
+            buffer: [0_u32; 16],
+        }
+    }
+
+    /// Constructor with a 256 bit key taken from the operating system (see [`crate::fill_entropy`]).
+    pub fn from_entropy() -> Self {
+        let mut key = [0_u8; 32];
+        fill_entropy(&mut key);
+        Self::from_key(key)
+    }
+
+    /// Returns the stream number.
+    pub fn stream(&self) -> u64 {
+        self.stream
+    }
+
+    /// Switches to another stream, keeping the position.
+    /// Streams of the same key are independent key streams.
+    pub fn set_stream(&mut self, stream: u64) {
+        self.stream = stream;
+        self.refill();
+    }
+
+    /// 位置 position を含むブロックを計算し直す
+    fn refill(&mut self) {
+        if self.position % BLOCK_WORDS_CHACHA != 0 {
+            self.buffer = self.block(self.position / BLOCK_WORDS_CHACHA);
+        }
+    }
+
+    /// ブロック counter の出力を計算する\
+    /// position は 2^68 未満に保つため、counter は 64 bit に収まる
+    fn block(&self, counter: u128) -> [u32; 16] {
+        let counter: u64 = counter as u64;
+        chacha20_block(
+            &self.key,
+            &[
+                counter as u32,
+                (counter >> 32) as u32,
+                self.stream as u32,
+                (self.stream >> 32) as u32,
+            ],
+        )
+    }
+}
+
+// 鍵が表示されないように、Debug は鍵を省略して実装する
+impl core::fmt::Debug for ChaCha20 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("ChaCha20")
+            .field("stream", &self.stream)
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CoreRng for ChaCha20 {
+    fn next_u32(&mut self) -> u32 {
+        let word: usize = (self.position % BLOCK_WORDS_CHACHA) as usize;
+        if word == 0_usize {
+            self.buffer = self.block(self.position / BLOCK_WORDS_CHACHA);
+        }
+        self.position = (self.position + 1_u128) % STREAM_WORDS_CHACHA;
+        self.buffer[word]
+    }
+
+    /// Derives the key from the seed sequence. Reproducible, but limited to the 64 bit state of the sequence.
+    fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        let mut key = [0_u8; 32];
+        for chunk in key.chunks_mut(8_usize) {
+            chunk.copy_from_slice(&seeds.next_u64().to_le_bytes());
+        }
+        Self::from_key(key)
+    }
+}
+
+impl RandomAccessRng for ChaCha20 {
+    /// Moves to `position` modulo 2^68, the length of a stream.
+    fn seek(&mut self, position: u128) {
+        self.position = position % STREAM_WORDS_CHACHA;
+        self.refill();
+    }
+
+    fn position(&self) -> u128 {
+        self.position
+    }
+}
+
+impl SplittableRng for ChaCha20 {
+    /// Moves to a stream derived from the current stream number with [`SeedSequence`], keeping the position.
+    /// Unlike `stream + 1`, the new stream does not collide with siblings created on consecutive stream numbers.
+    fn jump_substream(&mut self) {
+        self.set_stream(SeedSequence::from_u64(self.stream).next_u64());
+    }
+}
+
+/// RFC 8439 のテストベクトルと一致することを確認するテスト
+#[test]
+fn test_chacha20_block() {
+    // 2.3.2 節: ブロック関数のテストベクトル
+    let key: [u32; 8] = [
+        0x03020100, 0x07060504, 0x0b0a0908, 0x0f0e0d0c, 0x13121110, 0x17161514, 0x1b1a1918,
+        0x1f1e1d1c,
+    ];
+    assert_eq!(
+        chacha20_block(&key, &[0x00000001, 0x09000000, 0x4a000000, 0x00000000]),
+        [
+            0xe4e7f110, 0x15593bd1, 0x1fdd0f50, 0xc47120a3, 0xc7f4d1c7, 0x0368c033, 0x9aaa2204,
+            0x4e6cd4c3, 0x466482d2, 0x09aa9f07, 0x05d7c214, 0xa2028bd9, 0xd19c12b5, 0xb94e16de,
+            0xe883d0cb, 0x4e3c50a2
+        ]
+    );
+
+    // 付録 A.1 のテストベクトル #1: 鍵、カウンター、nonce が全て 0 の鍵ストリーム
+    let mut rng = ChaCha20::from_key([0_u8; 32]);
+    let mut key_stream = [0_u8; 64];
+    rng.fill_bytes(&mut key_stream);
+    assert_eq!(
+        key_stream[..16],
+        [
+            0x76, 0xb8, 0xe0, 0xad, 0xa0, 0xf1, 0x3d, 0x90, 0x40, 0x5d, 0x6a, 0xe5, 0x53, 0x86,
+            0xbd, 0x28
+        ]
+    );
+    assert_eq!(
+        key_stream[48..],
+        [
+            0x6a, 0x43, 0xb8, 0xf4, 0x15, 0x18, 0xa1, 0x1c, 0xc3, 0x87, 0xb6, 0x69, 0xb2, 0xee,
+            0x65, 0x86
+        ]
+    );
+}
+
+/// ストリームの切り替えと位置の移動が逐次計算と一致することを確認するテスト
+#[test]
+fn test_chacha20_streams() {
+    let mut sequential = ChaCha20::from_key([7_u8; 32]);
+    let values: Vec<u32> = (0..40).map(|_| sequential.next_u32()).collect();
+    let mut rng = ChaCha20::from_key([7_u8; 32]);
+    rng.seek(17_u128);
+    assert_eq!(rng.next_u32(), values[17]);
+
+    // 別のストリームは異なる鍵ストリームになり、分割すると親は次のストリームに移る
+    let mut parent = ChaCha20::from_key([7_u8; 32]);
+    let _ = parent.next_u32();
+    let mut child = parent.split();
+    assert_ne!(parent.stream(), 0_u64);
+    assert_ne!(parent.stream(), 1_u64);
+    assert_eq!(child.next_u32(), values[1]);
+    assert_ne!(parent.next_u32(), values[1]);
+
+    // 位置はストリームの長さ 2^68 語で一周する
+    let mut wrapped = ChaCha20::from_key([7_u8; 32]);
+    wrapped.seek(STREAM_WORDS_CHACHA + 17_u128);
+    assert_eq!(wrapped.position(), 17_u128);
+    assert_eq!(wrapped.next_u32(), values[17]);
+    wrapped.seek(STREAM_WORDS_CHACHA - 1_u128);
+    let _ = wrapped.next_u32();
+    assert_eq!(wrapped.position(), 0_u128);
+    assert_eq!(wrapped.next_u32(), values[0]);
+
+    // Debug 出力に鍵は含まれない
+    assert!(!format!("{parent:?}").contains("key"));
+}
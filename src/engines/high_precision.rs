@@ -0,0 +1,121 @@
+use crate::{CoreRng, SeedSequence, SplittableRng};
+
+/// Adapter that gives the uniform random numbers drawn from engine `R` the full 53 bit precision of `f64`.
+///
+/// By default, a uniform random number is one 32 bit output divided by `u32::MAX`, so it takes
+/// only 2^32 distinct values, and the distributions derived from it inherit that granularity
+/// (for example, the standard exponential distribution never exceeds about 22.2).
+/// Running a distribution on `HighPrecision<R>` draws a 53 bit mantissa with [`CoreRng::next_f64_53`] of `R`,
+/// which raises that limit to about 36.7. A 32 bit engine combines two outputs in the same way as
+/// `genrand_res53` of the Mersenne Twister, and a 64 bit engine takes the upper 53 bits of one `next_u64` output.
+///
+/// The raw outputs (`next_u32`, `next_u64` and `fill_bytes`) are those of `R`, unchanged.
+/// The uniform random numbers consume the outputs differently, so the sequences differ from those on `R`.
+///
+/// # Example
+/// ```
+/// use rand_simple::{Exponential, HighPrecision, Pcg32, Uniform, Xorshift160};
+///
+/// let mut exponential = Exponential::<HighPrecision<Xorshift160>>::from_rng_seed(1192_u64);
+/// println!("Returns a random number -> {}", exponential.sample());
+///
+/// let mut uniform = Uniform::from_rng(HighPrecision::new(Pcg32::new(42_u64, 54_u64)));
+/// println!("Returns a random number -> {}", uniform.sample());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighPrecision<R> {
+    rng: R, // 内部のエンジン
+}
+
+impl<R: CoreRng> HighPrecision<R> {
+    /// Constructor wrapping an engine.
+    /// * `rng` - Engine whose outputs are combined into 53 bit uniform random numbers
+    pub fn new(rng: R) -> Self {
+        Self { rng }
+    }
+
+    /// Returns a reference to the inner engine.
+    pub fn get_ref(&self) -> &R {
+        &self.rng
+    }
+
+    /// Unwraps the inner engine.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+}
+
+impl<R: CoreRng> CoreRng for HighPrecision<R> {
+    const HIGH_PRECISION: bool = true;
+
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn next_f64_53(&mut self) -> f64 {
+        self.rng.next_f64_53()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::new(R::from_seed_sequence(seeds))
+    }
+}
+
+impl<R: SplittableRng> SplittableRng for HighPrecision<R> {
+    /// Jumps the inner engine to its next substream.
+    fn jump_substream(&mut self) {
+        self.rng.jump_substream();
+    }
+}
+
+/// 53 bit の一様乱数が 32 bit の分解能を超え、genrand_res53 と一致することを確認するテスト
+#[test]
+fn test_high_precision() {
+    use crate::standard_distributions::{uniform_0_or_greater_and_less_than_1, uniform_0_to_1};
+    use crate::{Mt19937, Mt19937_64, Xorshift160, Xoshiro256StarStar};
+
+    // Python: random.seed(0x456 << 96 | 0x345 << 64 | 0x234 << 32 | 0x123); random.random()
+    let key: [u32; 4] = [0x123_u32, 0x234_u32, 0x345_u32, 0x456_u32];
+    let mut rng = HighPrecision::new(Mt19937::from_array(&key));
+    assert_eq!(
+        uniform_0_or_greater_and_less_than_1(&mut rng),
+        0.24856890158782508_f64
+    );
+    assert_eq!(
+        uniform_0_or_greater_and_less_than_1(&mut rng),
+        0.11112762955044497_f64
+    );
+
+    // 32 bit の一様乱数は u32::MAX 倍すると整数になるが、53 bit の一様乱数はならない
+    let mut rng = HighPrecision::new(Xorshift160::new(1192_u32));
+    let fractional: usize = (0..100)
+        .map(|_| uniform_0_to_1(&mut rng) * u32::MAX as f64)
+        .filter(|x| x.fract() != 0_f64)
+        .count();
+    assert!(fractional > 90_usize);
+
+    // 64 bit のエンジンは、1 つの出力の上位 53 bit を使用する
+    let mut inner = Xoshiro256StarStar::new(1192_u64);
+    let mut wrapped = HighPrecision::new(inner.clone());
+    for _ in 0..100 {
+        let expected: f64 = (inner.next_u64() >> 11) as f64 / 9_007_199_254_740_992_f64;
+        assert_eq!(uniform_0_to_1(&mut wrapped), expected);
+    }
+    let mut wrapped = HighPrecision::new(Mt19937_64::new(1192_u64));
+    let mut mt = Mt19937_64::new(1192_u64);
+    assert_eq!(uniform_0_to_1(&mut wrapped), mt.next_f64());
+
+    // 生の出力は内部のエンジンと同じ
+    let mut inner = Xorshift160::new(1192_u32);
+    let mut wrapped = HighPrecision::new(inner.clone());
+    assert_eq!(wrapped.next_u32(), inner.next_u32());
+    assert_eq!(wrapped.into_inner(), inner);
+}
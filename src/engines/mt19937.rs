@@ -0,0 +1,364 @@
+use crate::{CoreRng, SeedSequence};
+
+/// MT19937 の状態変数の語数
+const N_32: usize = 624;
+/// MT19937 のねじれの位置
+const M_32: usize = 397;
+/// MT19937 のねじれ行列の定数
+const MATRIX_A_32: u32 = 0x9908_B0DF;
+/// MT19937 の上位 1 bit のマスク
+const UPPER_MASK_32: u32 = 0x8000_0000;
+/// MT19937 の下位 31 bit のマスク
+const LOWER_MASK_32: u32 = 0x7FFF_FFFF;
+
+/// MT19937-64 の状態変数の語数
+const N_64: usize = 312;
+/// MT19937-64 のねじれの位置
+const M_64: usize = 156;
+/// MT19937-64 のねじれ行列の定数
+const MATRIX_A_64: u64 = 0xB502_6F5A_A966_19E9;
+/// MT19937-64 の上位 33 bit のマスク
+const UPPER_MASK_64: u64 = 0xFFFF_FFFF_8000_0000;
+/// MT19937-64 の下位 31 bit のマスク
+const LOWER_MASK_64: u64 = 0x7FFF_FFFF;
+
+/// MT19937 engine (Mersenne Twister, Matsumoto and Nishimura, 1998)
+///
+/// The 32 bit Mersenne Twister with a period of 2^19937 - 1. Its output matches the reference
+/// implementation `mt19937ar.c` bit for bit, and therefore other tools built on it:
+/// * [`Self::new`] seeds like `init_genrand`, as C++ `std::mt19937(seed)` and NumPy `RandomState(seed)` do
+/// * [`Self::from_array`] seeds like `init_by_array`, as NumPy `RandomState([...])` and Python `random.seed` do
+/// * [`Self::next_f64`] is `genrand_res53`, the uniform of NumPy `random_sample` and Python `random.random`
+/// * The distributions draw their uniform random numbers with `genrand_res53` too (see [`CoreRng::HIGH_PRECISION`]),
+///   so [`crate::Uniform`] matches NumPy `RandomState.uniform` and Python `random.uniform`
+///
+/// It is not recommended for new simulations, since it fails the linear-complexity tests
+/// and has a large state of 2.5 KB; use it to reproduce results of those tools.
+///
+/// # Example
+/// ```
+/// use rand_simple::{CoreRng, Mt19937, Uniform};
+///
+/// // Same as the first output of C++ std::mt19937 with the default seed
+/// let mut rng = Mt19937::new(5489_u32);
+/// assert_eq!(rng.next_u32(), 3_499_211_612_u32);
+///
+/// // Same as Python: random.seed(0x456 << 96 | 0x345 << 64 | 0x234 << 32 | 0x123); random.random()
+/// let mut rng = Mt19937::from_array(&[0x123_u32, 0x234_u32, 0x345_u32, 0x456_u32]);
+/// assert_eq!(rng.next_f64(), 0.24856890158782508_f64);
+///
+/// // Same as NumPy: np.random.RandomState(0).random_sample()
+/// let mut uniform = Uniform::from_rng(Mt19937::new(0_u32));
+/// assert_eq!(uniform.sample(), 0.5488135039273248_f64);
+/// ```
+/// # References
+/// * Matsumoto, M., & Nishimura, T. (1998). Mersenne twister: a 623-dimensionally equidistributed uniform pseudo-random number generator. ACM Transactions on Modeling and Computer Simulation, 8(1), 3–30.
+/// * URL: http://www.math.sci.hiroshima-u.ac.jp/m-mat/MT/MT2002/emt19937ar.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mt19937 {
+    state: [u32; N_32], // 状態変数
+    index: usize,       // 次に出力する状態変数の位置
+}
+
+impl Mt19937 {
+    /// Constructor with the seeding procedure of the reference implementation (`init_genrand`).
+    /// * `seed` - Seed; the reference implementation uses 5489 when no seed is given
+    pub fn new(seed: u32) -> Self {
+        let mut state: [u32; N_32] = [0_u32; N_32];
+        state[0] = seed;
+        for i in 1..N_32 {
+            state[i] = 1_812_433_253_u32
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+        Self { state, index: N_32 }
+    }
+
+    /// Constructor with the seeding procedure of the reference implementation (`init_by_array`).
+    /// * `key` - Seed array of any length; an empty array is treated as `[0]`, as Python does
+    pub fn from_array(key: &[u32]) -> Self {
+        let key: &[u32] = if key.is_empty() { &[0_u32] } else { key };
+        let mut rng = Self::new(19_650_218_u32);
+        let s = &mut rng.state;
+        let mut i: usize = 1_usize;
+        let mut j: usize = 0_usize;
+        for _ in 0..N_32.max(key.len()) {
+            s[i] = (s[i] ^ (s[i - 1] ^ (s[i - 1] >> 30)).wrapping_mul(1_664_525_u32))
+                .wrapping_add(key[j])
+                .wrapping_add(j as u32);
+            i += 1_usize;
+            j += 1_usize;
+            if i >= N_32 {
+                s[0] = s[N_32 - 1];
+                i = 1_usize;
+            }
+            if j >= key.len() {
+                j = 0_usize;
+            }
+        }
+        for _ in 0..N_32 - 1 {
+            s[i] = (s[i] ^ (s[i - 1] ^ (s[i - 1] >> 30)).wrapping_mul(1_566_083_941_u32))
+                .wrapping_sub(i as u32);
+            i += 1_usize;
+            if i >= N_32 {
+                s[0] = s[N_32 - 1];
+                i = 1_usize;
+            }
+        }
+        // 状態変数が全て 0 にならないことを保証する
+        s[0] = UPPER_MASK_32;
+        rng
+    }
+
+    /// Returns a uniform random number in [0, 1) with 53 bit resolution (`genrand_res53`).
+    pub fn next_f64(&mut self) -> f64 {
+        let a: u32 = self.next_u32() >> 5;
+        let b: u32 = self.next_u32() >> 6;
+        (f64::from(a) * 67_108_864_f64 + f64::from(b)) / 9_007_199_254_740_992_f64
+    }
+
+    /// 状態変数を N 語まとめて更新する
+    fn twist(&mut self) {
+        let s = &mut self.state;
+        for k in 0..N_32 {
+            let y: u32 = (s[k] & UPPER_MASK_32) | (s[(k + 1) % N_32] & LOWER_MASK_32);
+            let mag: u32 = if y & 1_u32 == 1_u32 {
+                MATRIX_A_32
+            } else {
+                0_u32
+            };
+            s[k] = s[(k + M_32) % N_32] ^ (y >> 1) ^ mag;
+        }
+        self.index = 0_usize;
+    }
+}
+
+impl CoreRng for Mt19937 {
+    /// Uniform random numbers combine two outputs as `genrand_res53` does.
+    const HIGH_PRECISION: bool = true;
+
+    fn next_u32(&mut self) -> u32 {
+        if self.index >= N_32 {
+            self.twist();
+        }
+        let mut y: u32 = self.state[self.index];
+        self.index += 1_usize;
+        // 調律 (tempering)
+        y ^= y >> 11;
+        y ^= (y << 7) & 0x9D2C_5680;
+        y ^= (y << 15) & 0xEFC6_0000;
+        y ^ (y >> 18)
+    }
+
+    /// Seeds with `init_by_array` on eight 32 bit words drawn from the seed sequence.
+    fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        let mut key: [u32; 8] = [0_u32; 8];
+        for pair in key.chunks_exact_mut(2_usize) {
+            let word: u64 = seeds.next_u64();
+            pair[0] = word as u32;
+            pair[1] = (word >> 32) as u32;
+        }
+        Self::from_array(&key)
+    }
+}
+
+/// MT19937-64 engine (64 bit Mersenne Twister, Nishimura, 2000)
+///
+/// The 64 bit variant of [`Mt19937`] with a period of 2^19937 - 1. Its output matches the reference
+/// implementation `mt19937-64.c` and C++ `std::mt19937_64`.
+///
+/// # Example
+/// ```
+/// use rand_simple::{CoreRng, Mt19937_64};
+///
+/// let mut rng = Mt19937_64::from_array(&[0x12345_u64, 0x23456_u64, 0x34567_u64, 0x45678_u64]);
+/// assert_eq!(rng.next_u64(), 7_266_447_313_870_364_031_u64);
+/// ```
+/// # References
+/// * Nishimura, T. (2000). Tables of 64-bit Mersenne twisters. ACM Transactions on Modeling and Computer Simulation, 10(4), 348–357.
+/// * URL: http://www.math.sci.hiroshima-u.ac.jp/m-mat/MT/emt64.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mt19937_64 {
+    state: [u64; N_64], // 状態変数
+    index: usize,       // 次に出力する状態変数の位置
+}
+
+impl Mt19937_64 {
+    /// Constructor with the seeding procedure of the reference implementation (`init_genrand64`).
+    /// * `seed` - Seed; the reference implementation uses 5489 when no seed is given
+    pub fn new(seed: u64) -> Self {
+        let mut state: [u64; N_64] = [0_u64; N_64];
+        state[0] = seed;
+        for i in 1..N_64 {
+            state[i] = 6_364_136_223_846_793_005_u64
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 62))
+                .wrapping_add(i as u64);
+        }
+        Self { state, index: N_64 }
+    }
+
+    /// Constructor with the seeding procedure of the reference implementation (`init_by_array64`).
+    /// * `key` - Seed array of any length; an empty array is treated as `[0]`
+    pub fn from_array(key: &[u64]) -> Self {
+        let key: &[u64] = if key.is_empty() { &[0_u64] } else { key };
+        let mut rng = Self::new(19_650_218_u64);
+        let s = &mut rng.state;
+        let mut i: usize = 1_usize;
+        let mut j: usize = 0_usize;
+        for _ in 0..N_64.max(key.len()) {
+            s[i] = (s[i]
+                ^ (s[i - 1] ^ (s[i - 1] >> 62)).wrapping_mul(3_935_559_000_370_003_845_u64))
+            .wrapping_add(key[j])
+            .wrapping_add(j as u64);
+            i += 1_usize;
+            j += 1_usize;
+            if i >= N_64 {
+                s[0] = s[N_64 - 1];
+                i = 1_usize;
+            }
+            if j >= key.len() {
+                j = 0_usize;
+            }
+        }
+        for _ in 0..N_64 - 1 {
+            s[i] = (s[i]
+                ^ (s[i - 1] ^ (s[i - 1] >> 62)).wrapping_mul(2_862_933_555_777_941_757_u64))
+            .wrapping_sub(i as u64);
+            i += 1_usize;
+            if i >= N_64 {
+                s[0] = s[N_64 - 1];
+                i = 1_usize;
+            }
+        }
+        // 状態変数が全て 0 にならないことを保証する
+        s[0] = 1_u64 << 63;
+        rng
+    }
+
+    /// Returns a uniform random number in [0, 1) with 53 bit resolution (`genrand64_res53`).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / 9_007_199_254_740_992_f64
+    }
+
+    /// 状態変数を N 語まとめて更新する
+    fn twist(&mut self) {
+        let s = &mut self.state;
+        for k in 0..N_64 {
+            let x: u64 = (s[k] & UPPER_MASK_64) | (s[(k + 1) % N_64] & LOWER_MASK_64);
+            let mag: u64 = if x & 1_u64 == 1_u64 {
+                MATRIX_A_64
+            } else {
+                0_u64
+            };
+            s[k] = s[(k + M_64) % N_64] ^ (x >> 1) ^ mag;
+        }
+        self.index = 0_usize;
+    }
+}
+
+impl CoreRng for Mt19937_64 {
+    /// Returns the upper 32 bits of [`Self::next_u64`].
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        if self.index >= N_64 {
+            self.twist();
+        }
+        let mut x: u64 = self.state[self.index];
+        self.index += 1_usize;
+        // 調律 (tempering)
+        x ^= (x >> 29) & 0x5555_5555_5555_5555;
+        x ^= (x << 17) & 0x71D6_7FFF_EDA6_0000;
+        x ^= (x << 37) & 0xFFF7_EEE0_0000_0000;
+        x ^ (x >> 43)
+    }
+
+    /// Returns the upper 53 bits of [`Self::next_u64`] as a uniform random number in [0, 1) (`genrand64_res53`).
+    fn next_f64_53(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / 9_007_199_254_740_992_f64
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8_usize) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    /// Seeds with `init_by_array64` on four 64 bit words drawn from the seed sequence.
+    fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        let key: [u64; 4] = std::array::from_fn(|_| seeds.next_u64());
+        Self::from_array(&key)
+    }
+}
+
+/// 参照実装 (mt19937ar.c, mt19937-64.c) の出力と一致することを確認するテスト
+#[test]
+fn test_mt19937() {
+    let mut mt = Mt19937::new(5489_u32);
+    assert_eq!(mt.next_u32(), 3_499_211_612_u32);
+    for _ in 1..9_999 {
+        let _ = mt.next_u32();
+    }
+    // C++ 標準 ([rand.predef]) が定める 10000 番目の出力
+    assert_eq!(mt.next_u32(), 4_123_659_995_u32);
+
+    let mut mt = Mt19937::from_array(&[0x123_u32, 0x234_u32, 0x345_u32, 0x456_u32]);
+    assert_eq!(
+        [(); 5].map(|_| mt.next_u32()),
+        [1067595299, 955945823, 477289528, 4107218783, 4228976476]
+    );
+
+    let mut mt = Mt19937::from_array(&[0x123_u32, 0x234_u32, 0x345_u32, 0x456_u32]);
+    assert_eq!(
+        [(); 2].map(|_| mt.next_f64()),
+        [0.24856890158782508_f64, 0.11112762955044497_f64]
+    );
+}
+
+/// 参照実装 (mt19937-64.c) の出力と一致することを確認するテスト
+#[test]
+fn test_mt19937_64() {
+    let mut mt = Mt19937_64::new(5489_u64);
+    assert_eq!(mt.next_u64(), 14_514_284_786_278_117_030_u64);
+    for _ in 1..9_999 {
+        let _ = mt.next_u64();
+    }
+    // C++ 標準 ([rand.predef]) が定める 10000 番目の出力
+    assert_eq!(mt.next_u64(), 9_981_545_732_273_789_042_u64);
+
+    let mut mt = Mt19937_64::from_array(&[0x12345_u64, 0x23456_u64, 0x34567_u64, 0x45678_u64]);
+    assert_eq!(
+        [(); 3].map(|_| mt.next_u64()),
+        [
+            7266447313870364031,
+            4946485549665804864,
+            16945909448695747420
+        ]
+    );
+}
+
+/// 一様分布が genrand_res53 を使用し、Python と NumPy の乱数列と一致することを確認するテスト
+#[test]
+fn test_mt19937_uniform() {
+    // Python: random.seed(0x456 << 96 | 0x345 << 64 | 0x234 << 32 | 0x123)
+    // random.random(), random.uniform(-2.0, 3.0)
+    let key: [u32; 4] = [0x123_u32, 0x234_u32, 0x345_u32, 0x456_u32];
+    let mut uniform = crate::Uniform::from_rng(Mt19937::from_array(&key));
+    assert_eq!(uniform.sample(), 0.24856890158782508_f64);
+    uniform.try_set_params(-2_f64, 3_f64).unwrap();
+    assert_eq!(uniform.sample(), -1.4443618522477752_f64);
+
+    // NumPy: np.random.RandomState(0).random_sample()
+    let mut uniform = crate::Uniform::from_rng(Mt19937::new(0_u32));
+    assert_eq!(uniform.sample(), 0.5488135039273248_f64);
+
+    // 全ての値が genrand_res53 と一致する
+    let mut uniform = crate::Uniform::from_rng(Mt19937::new(1192_u32));
+    let mut mt = Mt19937::new(1192_u32);
+    for _ in 0..100_000 {
+        assert_eq!(uniform.sample(), mt.next_f64());
+    }
+}
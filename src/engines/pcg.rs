@@ -0,0 +1,335 @@
+use crate::{CoreRng, SeedSequence, SplittableRng};
+
+/// PCG32 の線形合同法の乗数
+const MULTIPLIER_PCG32: u64 = 6_364_136_223_846_793_005_u64;
+
+/// PCG64 の線形合同法の乗数
+const MULTIPLIER_PCG64: u128 = 0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645_u128;
+
+/// 分割するときに、PCG32 の親の状態を進めるステップ数の指数 (2^48 ステップ)
+const SPLIT_JUMP_EXPONENT_PCG32: u32 = 48_u32;
+
+/// 分割するときに、PCG64 の親の状態を進めるステップ数の指数 (2^100 ステップ)
+const SPLIT_JUMP_EXPONENT_PCG64: u32 = 100_u32;
+
+/// 線形合同法 state <- state * multiplier + increment を delta ステップ進めた状態を返す\
+/// 繰り返し二乗法で O(log delta) で計算する (Brown, 1994)
+macro_rules! advance_lcg {
+    ($state:expr, $delta:expr, $multiplier:expr, $increment:expr, $one:expr) => {{
+        let mut accumulated_multiplier = $one;
+        let mut accumulated_increment = $one - $one;
+        let mut current_multiplier = $multiplier;
+        let mut current_increment = $increment;
+        let mut delta = $delta;
+        while delta > 0 {
+            if delta & 1 == 1 {
+                accumulated_multiplier = accumulated_multiplier.wrapping_mul(current_multiplier);
+                accumulated_increment = accumulated_increment
+                    .wrapping_mul(current_multiplier)
+                    .wrapping_add(current_increment);
+            }
+            current_increment = current_multiplier
+                .wrapping_add($one)
+                .wrapping_mul(current_increment);
+            current_multiplier = current_multiplier.wrapping_mul(current_multiplier);
+            delta >>= 1;
+        }
+        accumulated_multiplier
+            .wrapping_mul($state)
+            .wrapping_add(accumulated_increment)
+    }};
+}
+
+/// PCG32 engine (PCG-XSH-RR 64/32, O'Neill, 2014)
+///
+/// A 64 bit linear congruential generator with a permuted 32 bit output and a period of 2^64.
+/// Each of the 2^63 stream numbers selects a different sequence, which gives every parallel
+/// worker its own stream without coordination, and [`Self::advance`] moves to any position in O(log n).
+///
+/// # Example
+/// ```
+/// use rand_simple::{CoreRng, Normal, Pcg32};
+///
+/// // Output of the reference implementation for seed 42 and stream 54
+/// let mut rng = Pcg32::new(42_u64, 54_u64);
+/// assert_eq!(rng.next_u32(), 0xa15c02b7_u32);
+///
+/// // One stream per internal stream of a multi-stream distribution
+/// let mut normal = Normal::from_rngs([Pcg32::new(1192_u64, 0_u64), Pcg32::new(1192_u64, 1_u64)]);
+/// println!("Returns a random number -> {}", normal.sample());
+/// ```
+/// # References
+/// * O'Neill, M. E. (2014). PCG: A family of simple fast space-efficient statistically good algorithms for random number generation. Harvey Mudd College, HMC-CS-2014-0905.
+/// * URL: https://www.pcg-random.org/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pcg32 {
+    state: u64,     // 状態変数
+    increment: u64, // 加算定数 (ストリーム番号から作成する奇数)
+}
+
+impl Pcg32 {
+    /// Constructor with the seeding procedure of the reference implementation (`pcg32_srandom_r`).
+    /// * `seed` - Initial state
+    /// * `stream` - Stream number; the top bit is ignored
+    pub fn new(seed: u64, stream: u64) -> Self {
+        let mut rng = Self::from_state(0_u64, stream);
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    /// Constructor from the internal state and the stream number, without the seeding steps of [`Self::new`].
+    pub fn from_state(state: u64, stream: u64) -> Self {
+        Self {
+            state,
+            increment: (stream << 1) | 1_u64,
+        }
+    }
+
+    /// Returns the internal state.
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// Returns the stream number.
+    pub fn stream(&self) -> u64 {
+        self.increment >> 1
+    }
+
+    /// Switches to another stream, keeping the internal state.
+    /// * `stream` - Stream number; the top bit is ignored
+    pub fn set_stream(&mut self, stream: u64) {
+        self.increment = (stream << 1) | 1_u64;
+    }
+
+    /// Advances the state by `delta` steps in O(log(delta)).
+    /// Since the period is 2^64, `advance(delta.wrapping_neg())` goes back by `delta` steps.
+    pub fn advance(&mut self, delta: u64) {
+        self.state = advance_lcg!(self.state, delta, MULTIPLIER_PCG32, self.increment, 1_u64);
+    }
+
+    /// 線形合同法で状態変数を1ステップ進める
+    #[inline]
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(MULTIPLIER_PCG32)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl CoreRng for Pcg32 {
+    fn next_u32(&mut self) -> u32 {
+        let old_state: u64 = self.state;
+        self.step();
+        // XSH-RR: 上位ビットの xorshift の後、上位 5 bit で決まる量だけ右回転する
+        let xor_shifted: u32 = (((old_state >> 18) ^ old_state) >> 27) as u32;
+        let rotation: u32 = (old_state >> 59) as u32;
+        xor_shifted.rotate_right(rotation)
+    }
+
+    fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::new(seeds.next_u64(), seeds.next_u64())
+    }
+}
+
+impl SplittableRng for Pcg32 {
+    /// Advances the state by 2^48 steps, keeping the stream.
+    /// Every stream visits all 2^64 states, so a jump on the same stream is what keeps the substreams apart:
+    /// they do not overlap while each draws fewer than 2^48 values and fewer than 2^16 are split off.
+    fn jump_substream(&mut self) {
+        self.advance(1_u64 << SPLIT_JUMP_EXPONENT_PCG32);
+    }
+}
+
+/// PCG64 engine (PCG-XSL-RR 128/64, O'Neill, 2014)
+///
+/// A 128 bit linear congruential generator with a permuted 64 bit output and a period of 2^128.
+/// Like [`Pcg32`], it offers 2^127 streams and O(log n) [`Self::advance`].
+///
+/// # Example
+/// ```
+/// use rand_simple::{CoreRng, Gamma, Pcg64};
+///
+/// // Output of the reference implementation for seed 42 and stream 54
+/// let mut rng = Pcg64::new(42_u128, 54_u128);
+/// assert_eq!(rng.next_u64(), 0x86b1da1d72062b68_u64);
+///
+/// let mut gamma = Gamma::<Pcg64>::from_rng_seed(1192_u64);
+/// println!("Returns a random number -> {}", gamma.sample());
+/// ```
+/// # References
+/// * O'Neill, M. E. (2014). PCG: A family of simple fast space-efficient statistically good algorithms for random number generation. Harvey Mudd College, HMC-CS-2014-0905.
+/// * URL: https://www.pcg-random.org/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pcg64 {
+    state: u128,     // 状態変数
+    increment: u128, // 加算定数 (ストリーム番号から作成する奇数)
+}
+
+impl Pcg64 {
+    /// Constructor with the seeding procedure of the reference implementation (`pcg64_srandom_r`).
+    /// * `seed` - Initial state
+    /// * `stream` - Stream number; the top bit is ignored
+    pub fn new(seed: u128, stream: u128) -> Self {
+        let mut rng = Self::from_state(0_u128, stream);
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    /// Constructor from the internal state and the stream number, without the seeding steps of [`Self::new`].
+    pub fn from_state(state: u128, stream: u128) -> Self {
+        Self {
+            state,
+            increment: (stream << 1) | 1_u128,
+        }
+    }
+
+    /// Returns the internal state.
+    pub fn state(&self) -> u128 {
+        self.state
+    }
+
+    /// Returns the stream number.
+    pub fn stream(&self) -> u128 {
+        self.increment >> 1
+    }
+
+    /// Switches to another stream, keeping the internal state.
+    /// * `stream` - Stream number; the top bit is ignored
+    pub fn set_stream(&mut self, stream: u128) {
+        self.increment = (stream << 1) | 1_u128;
+    }
+
+    /// Advances the state by `delta` steps in O(log(delta)).
+    /// Since the period is 2^128, `advance(delta.wrapping_neg())` goes back by `delta` steps.
+    pub fn advance(&mut self, delta: u128) {
+        self.state = advance_lcg!(self.state, delta, MULTIPLIER_PCG64, self.increment, 1_u128);
+    }
+
+    /// 線形合同法で状態変数を1ステップ進める
+    #[inline]
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(MULTIPLIER_PCG64)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl CoreRng for Pcg64 {
+    /// Returns the upper 32 bits of [`Self::next_u64`].
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.step();
+        // XSL-RR: 上位と下位の 64 bit の排他的論理和を、上位 6 bit で決まる量だけ右回転する
+        let xor_folded: u64 = ((self.state >> 64) as u64) ^ (self.state as u64);
+        let rotation: u32 = (self.state >> 122) as u32;
+        xor_folded.rotate_right(rotation)
+    }
+
+    /// Returns the upper 53 bits of [`Self::next_u64`] as a uniform random number in [0, 1).
+    fn next_f64_53(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / 9_007_199_254_740_992_f64
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8_usize) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        let mut next_u128 = || (u128::from(seeds.next_u64()) << 64) | u128::from(seeds.next_u64());
+        let seed: u128 = next_u128();
+        let stream: u128 = next_u128();
+        Self::new(seed, stream)
+    }
+}
+
+impl SplittableRng for Pcg64 {
+    /// Advances the state by 2^100 steps, keeping the stream.
+    /// The substreams do not overlap while each draws fewer than 2^100 values and fewer than 2^28 are split off.
+    fn jump_substream(&mut self) {
+        self.advance(1_u128 << SPLIT_JUMP_EXPONENT_PCG64);
+    }
+}
+
+/// 参照実装 (pcg-c) の出力と一致することを確認するテスト
+#[test]
+fn test_pcg() {
+    let mut pcg32 = Pcg32::new(42_u64, 54_u64);
+    assert_eq!(
+        [(); 6].map(|_| pcg32.next_u32()),
+        [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e]
+    );
+    assert_eq!(pcg32.stream(), 54_u64);
+
+    let mut pcg64 = Pcg64::new(42_u128, 54_u128);
+    assert_eq!(
+        [(); 3].map(|_| pcg64.next_u64()),
+        [0x86b1da1d72062b68, 0x1304aa46c9853d39, 0xa3670e9e0dd50358]
+    );
+}
+
+/// advance が逐次計算と一致し、負のステップで戻れることを確認するテスト
+#[test]
+fn test_pcg_advance() {
+    let mut sequential = Pcg32::new(42_u64, 54_u64);
+    let mut advanced = sequential.clone();
+    for _ in 0..1_000 {
+        let _ = sequential.next_u32();
+    }
+    advanced.advance(1_000_u64);
+    assert_eq!(advanced, sequential);
+    assert_eq!(advanced.next_u32(), 4_025_215_667_u32);
+    advanced.advance(1_001_u64.wrapping_neg());
+    assert_eq!(advanced, Pcg32::new(42_u64, 54_u64));
+
+    let mut sequential = Pcg64::new(42_u128, 54_u128);
+    let mut advanced = sequential.clone();
+    for _ in 0..1_000 {
+        let _ = sequential.next_u64();
+    }
+    advanced.advance(1_000_u128);
+    assert_eq!(advanced, sequential);
+}
+
+/// 分割すると、子は親の状態を引き継ぎ、親は同じストリーム上で固定のステップ数だけ進むことを確認するテスト
+#[test]
+fn test_pcg_split() {
+    use crate::SplittableDistribution;
+    let mut parent =
+        crate::Beta::from_rngs(std::array::from_fn(|i| Pcg32::new(1192_u64, i as u64)));
+    let mut child = parent.split();
+    let mut different: usize = 0_usize;
+    for _ in 0..100 {
+        if parent.sample() != child.sample() {
+            different += 1_usize;
+        }
+    }
+    assert_eq!(different, 100_usize);
+
+    let mut rng = Pcg32::new(42_u64, 54_u64);
+    let child = rng.split();
+    assert_eq!(child, Pcg32::new(42_u64, 54_u64));
+    let mut expected = child.clone();
+    expected.advance(1_u64 << SPLIT_JUMP_EXPONENT_PCG32);
+    assert_eq!(rng, expected);
+    assert_eq!(rng.stream(), 54_u64);
+
+    let mut rng = Pcg64::new(42_u128, 54_u128);
+    let child = rng.split();
+    assert_eq!(child, Pcg64::new(42_u128, 54_u128));
+    let mut expected = child.clone();
+    expected.advance(1_u128 << SPLIT_JUMP_EXPONENT_PCG64);
+    assert_eq!(rng, expected);
+    assert_eq!(rng.stream(), 54_u128);
+}
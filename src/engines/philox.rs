@@ -0,0 +1,198 @@
+use crate::{CoreRng, RandomAccessRng, SeedSequence, SplittableRng};
+
+// Philox4x32 の乗数と鍵の加算定数 (黄金比、√3 - 1)
+const M0_PHILOX: u32 = 0xD251_1F53_u32;
+const M1_PHILOX: u32 = 0xCD9E_8D57_u32;
+const W0_PHILOX: u32 = 0x9E37_79B9_u32;
+const W1_PHILOX: u32 = 0xBB67_AE85_u32;
+
+/// Philox4x32 のラウンド数
+const ROUNDS_PHILOX: usize = 10_usize;
+
+/// 分割するときに、親の位置を進める語数の指数 (2^100 語)
+const SPLIT_JUMP_EXPONENT_PHILOX: u32 = 100_u32;
+
+/// Philox4x32-10 engine (Salmon et al., 2011)
+///
+/// A counter-based engine: word `i` of stream `key` is a bijective scramble of the counter `i / 4`
+/// under the key, so any position can be evaluated directly with [`Self::seek`] or
+/// [`Self::block`] without generating the preceding values. Each of the 2^64 keys gives an
+/// independent stream of 2^128 words, after which the position wraps around to 0.
+///
+/// # Example
+/// ```
+/// use rand_simple::{CoreRng, Philox4x32, RandomAccessRng};
+///
+/// // Known-answer test of the reference implementation (Random123)
+/// assert_eq!(Philox4x32::block(0_u128, 0_u64), [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]);
+///
+/// // Value 1,000,000 of stream 7, without generating values 0..1,000,000
+/// let mut rng = Philox4x32::new(7_u64);
+/// rng.seek(1_000_000_u128);
+/// println!("Returns a random number -> {}", rng.next_u32());
+/// ```
+/// # References
+/// * Salmon, J. K., Moraes, M. A., Dror, R. O., & Shaw, D. E. (2011). Parallel random numbers: As easy as 1, 2, 3. Proceedings of the International Conference for High Performance Computing, Networking, Storage and Analysis, 1–12.
+/// * URL: https://doi.org/10.1145/2063384.2063405
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Philox4x32 {
+    key: u64,         // 鍵 (ストリーム番号)
+    position: u128,   // 次に返す語の位置
+    buffer: [u32; 4], // 位置 position を含むブロックの出力 (position が 4 の倍数のときは未使用)
+}
+
+impl Philox4x32 {
+    /// Constructor positioned at the start of stream `key`
+    /// * `key` - Key (stream number)
+    pub fn new(key: u64) -> Self {
+        Self {
+            key,
+            position: 0_u128,
+            buffer: [0_u32; 4],
+        }
+    }
+
+    /// Returns the four output words of block `counter` of stream `key`,
+    /// which are the words at positions `4 * counter` to `4 * counter + 3`.
+    pub fn block(counter: u128, key: u64) -> [u32; 4] {
+        let mut c: [u32; 4] = [
+            counter as u32,
+            (counter >> 32) as u32,
+            (counter >> 64) as u32,
+            (counter >> 96) as u32,
+        ];
+        let mut k: [u32; 2] = [key as u32, (key >> 32) as u32];
+        for round in 0..ROUNDS_PHILOX {
+            if round > 0 {
+                k = [k[0].wrapping_add(W0_PHILOX), k[1].wrapping_add(W1_PHILOX)];
+            }
+            let product_0: u64 = u64::from(M0_PHILOX) * u64::from(c[0]);
+            let product_1: u64 = u64::from(M1_PHILOX) * u64::from(c[2]);
+            c = [
+                (product_1 >> 32) as u32 ^ c[1] ^ k[0],
+                product_1 as u32,
+                (product_0 >> 32) as u32 ^ c[3] ^ k[1],
+                product_0 as u32,
+            ];
+        }
+        c
+    }
+
+    /// Returns the key (stream number).
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+}
+
+impl CoreRng for Philox4x32 {
+    fn next_u32(&mut self) -> u32 {
+        let word: usize = (self.position % 4) as usize;
+        if word == 0_usize {
+            self.buffer = Self::block(self.position / 4, self.key);
+        }
+        self.position = self.position.wrapping_add(1_u128);
+        self.buffer[word]
+    }
+
+    fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self::new(seeds.next_u64())
+    }
+}
+
+impl RandomAccessRng for Philox4x32 {
+    fn seek(&mut self, position: u128) {
+        self.position = position;
+        if position % 4 != 0 {
+            self.buffer = Self::block(position / 4, self.key);
+        }
+    }
+
+    fn position(&self) -> u128 {
+        self.position
+    }
+}
+
+impl SplittableRng for Philox4x32 {
+    /// Advances the position by 2^100 words.
+    fn jump_substream(&mut self) {
+        self.seek(
+            self.position
+                .wrapping_add(1_u128 << SPLIT_JUMP_EXPONENT_PHILOX),
+        );
+    }
+}
+
+/// 参照実装 (Random123) の Known-answer test と一致することを確認するテスト
+#[test]
+fn test_philox4x32() {
+    assert_eq!(
+        Philox4x32::block(0_u128, 0_u64),
+        [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]
+    );
+    assert_eq!(
+        Philox4x32::block(u128::MAX, u64::MAX),
+        [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd]
+    );
+    assert_eq!(
+        Philox4x32::block(
+            0x0370_7344_1319_8A2E_85A3_08D3_243F_6A88_u128,
+            0x299F_31D0_A409_3822_u64
+        ),
+        [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1]
+    );
+
+    let mut rng = Philox4x32::new(1192_u64);
+    assert_eq!(
+        [(); 6].map(|_| rng.next_u32()),
+        [1457061372, 457126436, 2694737897, 1105892927, 40067460, 2496999531]
+    );
+}
+
+/// 任意の位置へ移動した場合に、逐次計算と同じ値が得られることを確認するテスト
+#[test]
+fn test_philox4x32_seek() {
+    let mut sequential = Philox4x32::new(7_u64);
+    let values: Vec<u32> = (0..103).map(|_| sequential.next_u32()).collect();
+    for position in [0_usize, 1, 2, 3, 4, 5, 50, 101] {
+        let mut rng = Philox4x32::new(7_u64);
+        rng.seek(position as u128);
+        assert_eq!(rng.position(), position as u128);
+        assert_eq!(rng.next_u32(), values[position]);
+        assert_eq!(rng.next_u32(), values[position + 1]);
+    }
+}
+
+/// 逆関数法の確率分布で、任意の位置の乱数が逐次計算と一致することを確認するテスト
+#[test]
+fn test_sample_at() {
+    macro_rules! check_sample_at {
+        ($distribution:expr) => {{
+            let mut sequential = $distribution;
+            let random_access = sequential.clone();
+            for index in 0_u128..200_u128 {
+                assert_eq!(random_access.sample_at(index), sequential.sample());
+            }
+            // sample_at は状態を変更しない
+            let mut fresh = random_access.clone();
+            assert_eq!(random_access.sample_at(0_u128), fresh.sample());
+        }};
+    }
+    let rng = Philox4x32::new(1192_u64);
+    check_sample_at!(crate::Exponential::from_rng(rng.clone()));
+    check_sample_at!(crate::Laplace::from_rng(rng.clone()));
+    check_sample_at!(crate::Cauchy::from_rng(rng.clone()));
+    check_sample_at!(crate::HalfCauchy::from_rng(rng.clone()));
+    check_sample_at!(crate::LogLaplace::from_rng(rng.clone()));
+    check_sample_at!(crate::Rayleigh::from_rng(rng.clone()));
+    check_sample_at!(crate::Frechet::from_rng(rng.clone()));
+    check_sample_at!(crate::ReflectedWeibull::from_rng(rng.clone()));
+    check_sample_at!(crate::PowerFunction::from_rng(rng.clone()));
+    check_sample_at!(crate::Uniform::from_rng(rng.clone()));
+    check_sample_at!(crate::Weibull::from_rng(rng.clone()));
+    check_sample_at!(crate::Gunbel::from_rng(rng.clone()));
+    check_sample_at!(crate::Pareto::from_rng(rng.clone()));
+    check_sample_at!(crate::Lomax::from_rng(rng.clone()));
+    check_sample_at!(crate::GeneralizedPareto::from_rng(rng.clone()));
+    check_sample_at!(crate::Logistic::from_rng(rng.clone()));
+    check_sample_at!(crate::LogLogistic::from_rng(rng));
+}
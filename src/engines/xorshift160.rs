@@ -0,0 +1,155 @@
+use crate::standard_distributions::{
+    generate_random_state, xorshift160, xorshift160_jump, xorshift160_jump_pow2,
+    SPLIT_JUMP_EXPONENT,
+};
+use crate::{CoreRng, ParameterUpdateError, SeedSequence, SplittableRng};
+
+/// Xorshift160 engine (Marsaglia, 2003)
+///
+/// The default engine of every distribution in this crate. It has a period of 2^160 - 1
+/// and returns 32 bits per call.
+///
+/// # Example
+/// ```
+/// use rand_simple::{CoreRng, Xorshift160};
+///
+/// let mut rng = Xorshift160::new(1192_u32);
+/// assert_eq!(rng.state(), [123456789, 362436069, 521288629, 88675123, 1192]);
+/// assert_eq!(rng.next_u32(), 2864191173_u32);
+///
+/// // Jumping ahead is equivalent to drawing the values one by one
+/// let mut jumped = Xorshift160::new(1192_u32);
+/// jumped.jump(1_u128);
+/// assert_eq!(jumped, rng);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xorshift160 {
+    xyzuv: [u32; 5], // 状態変数
+}
+
+impl Xorshift160 {
+    /// Constructor with the legacy seeding scheme of the distributions' `new` (see [`crate::legacy_state`])
+    /// * `seed` - Random seed
+    pub fn new(seed: u32) -> Self {
+        Self {
+            xyzuv: generate_random_state(seed),
+        }
+    }
+
+    /// Constructor from the state variables `(x, y, z, u, v)`
+    ///
+    /// Fails with [`ParameterUpdateError::InvalidState`] if every state variable is 0,
+    /// since the engine would then return 0 forever.
+    pub fn from_state(state: [u32; 5]) -> Result<Self, ParameterUpdateError> {
+        if state == [0_u32; 5] {
+            Err(ParameterUpdateError::InvalidState)
+        } else {
+            Ok(Self { xyzuv: state })
+        }
+    }
+
+    /// Constructs one engine per state with [`Self::from_state`], for the distributions with several streams.
+    pub(crate) fn from_states<const N: usize>(
+        states: [[u32; 5]; N],
+    ) -> Result<[Self; N], ParameterUpdateError> {
+        if states.contains(&[0_u32; 5]) {
+            Err(ParameterUpdateError::InvalidState)
+        } else {
+            Ok(states.map(|xyzuv| Self { xyzuv }))
+        }
+    }
+
+    /// Returns the state variables `(x, y, z, u, v)`.
+    pub fn state(&self) -> [u32; 5] {
+        self.xyzuv
+    }
+
+    /// Advances the state by `steps` steps at once (see [`crate::xorshift160_jump`]).
+    pub fn jump(&mut self, steps: u128) {
+        xorshift160_jump(&mut self.xyzuv, steps);
+    }
+
+    /// Advances the state by `2^exponent` steps at once (see [`crate::xorshift160_jump_pow2`]).
+    pub fn jump_pow2(&mut self, exponent: u32) {
+        xorshift160_jump_pow2(&mut self.xyzuv, exponent);
+    }
+}
+
+impl CoreRng for Xorshift160 {
+    fn next_u32(&mut self) -> u32 {
+        xorshift160(&mut self.xyzuv)
+    }
+
+    fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        // 乱数の種の列は全て 0 の状態変数を返さない
+        Self {
+            xyzuv: seeds.next_state(),
+        }
+    }
+}
+
+impl SplittableRng for Xorshift160 {
+    /// Jumps ahead by 2^100 steps.
+    fn jump_substream(&mut self) {
+        self.jump_pow2(SPLIT_JUMP_EXPONENT);
+    }
+}
+
+/// エンジンを明示して作成した乱数生成器が従来の乱数列と一致することを確認するテスト
+#[test]
+fn test_xorshift160_engine() {
+    let mut legacy = crate::Normal::new([1192_u32, 765_u32]);
+    let mut engine =
+        crate::Normal::from_rngs([Xorshift160::new(1192_u32), Xorshift160::new(765_u32)]);
+    for _ in 0..100 {
+        assert_eq!(legacy.sample(), engine.sample());
+    }
+
+    // 乱数の種の列から作成した場合も、エンジンを明示しない場合と一致する
+    assert_eq!(
+        crate::Beta::from_seed(1192_u64),
+        crate::Beta::<Xorshift160>::from_rng_seed(1192_u64)
+    );
+
+    // 分割すると、エンジンは 2^100 ステップ進む
+    let mut rng = Xorshift160::new(1192_u32);
+    let child = rng.split();
+    let mut expected = child.clone();
+    expected.jump_pow2(SPLIT_JUMP_EXPONENT);
+    assert_eq!(child, Xorshift160::new(1192_u32));
+    assert_eq!(rng, expected);
+}
+
+/// 全て 0 の状態変数から復元できないことを確認するテスト
+#[test]
+fn test_xorshift160_zero_state() {
+    assert_eq!(
+        Xorshift160::from_state([0_u32; 5]),
+        Err(ParameterUpdateError::InvalidState)
+    );
+    assert_eq!(
+        Xorshift160::from_state([0_u32, 0_u32, 0_u32, 0_u32, 1_u32]),
+        Ok(Xorshift160 {
+            xyzuv: [0_u32, 0_u32, 0_u32, 0_u32, 1_u32]
+        })
+    );
+
+    // 分布の from_state も、パラメータを設定する前にエラーを返す
+    let params = crate::LaplaceParams {
+        location: 0_f64,
+        scale: 1_f64,
+    };
+    assert_eq!(
+        crate::Laplace::from_state([0_u32; 5], params),
+        Err(ParameterUpdateError::InvalidState)
+    );
+    let params = crate::NormalParams {
+        mean: 0_f64,
+        std: 1_f64,
+    };
+    let state = crate::Normal::new([1192_u32, 765_u32]).state();
+    assert_eq!(
+        crate::Normal::from_state([state[0], [0_u32; 5]], params),
+        Err(ParameterUpdateError::InvalidState)
+    );
+}
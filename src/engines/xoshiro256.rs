@@ -0,0 +1,337 @@
+use crate::{CoreRng, ParameterUpdateError, SeedSequence, SplittableRng};
+
+/// jump で使用する多項式 (2^128 ステップ)
+const JUMP: [u64; 4] = [
+    0x180E_C6D3_3CFD_0ABA,
+    0xD5A6_1266_F0C9_392C,
+    0xA958_2618_E03F_C9AA,
+    0x39AB_DC45_29B1_661C,
+];
+
+/// long_jump で使用する多項式 (2^192 ステップ)
+const LONG_JUMP: [u64; 4] = [
+    0x76E1_5D3E_FEFD_CBBF,
+    0xC500_4E44_1C52_2FB3,
+    0x7771_0069_854E_E241,
+    0x3910_9BB0_2ACB_E635,
+];
+
+/// xoshiro256 の状態遷移 (xoshiro256** と xoshiro256+ で共通)
+#[inline]
+fn step(s: &mut [u64; 4]) {
+    let t: u64 = s[1] << 17;
+    s[2] ^= s[0];
+    s[3] ^= s[1];
+    s[1] ^= s[2];
+    s[0] ^= s[3];
+    s[2] ^= t;
+    s[3] = s[3].rotate_left(45);
+}
+
+/// 多項式 jump を状態変数に作用させて、状態を一度に進める
+fn jump_with(s: &mut [u64; 4], jump: &[u64; 4]) {
+    let mut accumulator: [u64; 4] = [0_u64; 4];
+    for word in jump.iter() {
+        for bit in 0..64 {
+            if (word >> bit) & 1_u64 == 1_u64 {
+                for (a_i, s_i) in accumulator.iter_mut().zip(s.iter()) {
+                    *a_i ^= s_i;
+                }
+            }
+            step(s);
+        }
+    }
+    *s = accumulator;
+}
+
+/// 乱数の種の列から、全て 0 ではない状態変数を作成する
+fn state_from_seed_sequence(seeds: &mut SeedSequence) -> [u64; 4] {
+    loop {
+        let s: [u64; 4] = std::array::from_fn(|_| seeds.next_u64());
+        if s != [0_u64; 4] {
+            return s;
+        }
+    }
+}
+
+/// xoshiro256** engine (Blackman and Vigna, 2018)
+///
+/// An all-purpose 64 bit engine with a period of 2^256 - 1. Every output bit passes the
+/// linear-complexity tests that Xorshift160 fails.
+///
+/// # Example
+/// ```
+/// use rand_simple::{CoreRng, Normal, Xoshiro256StarStar};
+///
+/// let mut rng = Xoshiro256StarStar::from_state([1_u64, 2_u64, 3_u64, 4_u64]).unwrap();
+/// assert_eq!(rng.next_u64(), 11520_u64);
+///
+/// // Run any distribution on xoshiro256**
+/// let mut normal = Normal::<Xoshiro256StarStar>::from_rng_seed(1192_u64);
+/// println!("Returns a random number -> {}", normal.sample());
+/// ```
+/// # References
+/// * Blackman, D., & Vigna, S. (2021). Scrambled linear pseudorandom number generators. ACM Transactions on Mathematical Software, 47(4), 1–32.
+/// * URL: https://prng.di.unimi.it/xoshiro256starstar.c
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xoshiro256StarStar {
+    s: [u64; 4], // 状態変数
+}
+
+impl Xoshiro256StarStar {
+    /// Constructor that expands a 64 bit seed into the state with SplitMix64, as recommended by the authors.
+    /// * `seed` - Random seed
+    pub fn new(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor from the state variables
+    ///
+    /// Fails with [`ParameterUpdateError::InvalidState`] if every state variable is 0,
+    /// since the engine would then return 0 forever.
+    pub fn from_state(state: [u64; 4]) -> Result<Self, ParameterUpdateError> {
+        if state == [0_u64; 4] {
+            Err(ParameterUpdateError::InvalidState)
+        } else {
+            Ok(Self { s: state })
+        }
+    }
+
+    /// Returns the state variables.
+    pub fn state(&self) -> [u64; 4] {
+        self.s
+    }
+
+    /// Advances the state by 2^128 steps at once.
+    /// Generates 2^128 non-overlapping substreams for parallel computations.
+    pub fn jump(&mut self) {
+        jump_with(&mut self.s, &JUMP);
+    }
+
+    /// Advances the state by 2^192 steps at once.
+    /// Generates 2^64 starting points, each of which can be divided further with [`Self::jump`].
+    pub fn long_jump(&mut self) {
+        jump_with(&mut self.s, &LONG_JUMP);
+    }
+}
+
+impl CoreRng for Xoshiro256StarStar {
+    /// Returns the upper 32 bits of [`Self::next_u64`].
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let result: u64 = self.s[1]
+            .wrapping_mul(5_u64)
+            .rotate_left(7)
+            .wrapping_mul(9_u64);
+        step(&mut self.s);
+        result
+    }
+
+    /// Returns the upper 53 bits of [`Self::next_u64`] as a uniform random number in [0, 1).
+    fn next_f64_53(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / 9_007_199_254_740_992_f64
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8_usize) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self {
+            s: state_from_seed_sequence(seeds),
+        }
+    }
+}
+
+impl SplittableRng for Xoshiro256StarStar {
+    /// Jumps ahead by 2^128 steps.
+    fn jump_substream(&mut self) {
+        self.jump();
+    }
+}
+
+/// xoshiro256+ engine (Blackman and Vigna, 2018)
+///
+/// A slightly faster variant of [`Xoshiro256StarStar`] intended for floating-point generation.
+/// The lowest bits of its output have low linear complexity, so [`CoreRng::next_u32`] returns
+/// the upper 32 bits.
+///
+/// # Example
+/// ```
+/// use rand_simple::{CoreRng, Uniform, Xoshiro256Plus};
+///
+/// let mut rng = Xoshiro256Plus::from_state([1_u64, 2_u64, 3_u64, 4_u64]).unwrap();
+/// assert_eq!(rng.next_u64(), 5_u64);
+///
+/// let mut uniform = Uniform::from_rng(Xoshiro256Plus::new(1192_u64));
+/// println!("Returns a random number -> {}", uniform.sample());
+/// ```
+/// # References
+/// * Blackman, D., & Vigna, S. (2021). Scrambled linear pseudorandom number generators. ACM Transactions on Mathematical Software, 47(4), 1–32.
+/// * URL: https://prng.di.unimi.it/xoshiro256plus.c
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xoshiro256Plus {
+    s: [u64; 4], // 状態変数
+}
+
+impl Xoshiro256Plus {
+    /// Constructor that expands a 64 bit seed into the state with SplitMix64, as recommended by the authors.
+    /// * `seed` - Random seed
+    pub fn new(seed: u64) -> Self {
+        Self::from_seed_sequence(&mut SeedSequence::from_u64(seed))
+    }
+
+    /// Constructor from the state variables
+    ///
+    /// Fails with [`ParameterUpdateError::InvalidState`] if every state variable is 0,
+    /// since the engine would then return 0 forever.
+    pub fn from_state(state: [u64; 4]) -> Result<Self, ParameterUpdateError> {
+        if state == [0_u64; 4] {
+            Err(ParameterUpdateError::InvalidState)
+        } else {
+            Ok(Self { s: state })
+        }
+    }
+
+    /// Returns the state variables.
+    pub fn state(&self) -> [u64; 4] {
+        self.s
+    }
+
+    /// Advances the state by 2^128 steps at once.
+    /// Generates 2^128 non-overlapping substreams for parallel computations.
+    pub fn jump(&mut self) {
+        jump_with(&mut self.s, &JUMP);
+    }
+
+    /// Advances the state by 2^192 steps at once.
+    /// Generates 2^64 starting points, each of which can be divided further with [`Self::jump`].
+    pub fn long_jump(&mut self) {
+        jump_with(&mut self.s, &LONG_JUMP);
+    }
+}
+
+impl CoreRng for Xoshiro256Plus {
+    /// Returns the upper 32 bits of [`Self::next_u64`].
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let result: u64 = self.s[0].wrapping_add(self.s[3]);
+        step(&mut self.s);
+        result
+    }
+
+    /// Returns the upper 53 bits of [`Self::next_u64`] as a uniform random number in [0, 1).
+    fn next_f64_53(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / 9_007_199_254_740_992_f64
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8_usize) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn from_seed_sequence(seeds: &mut SeedSequence) -> Self {
+        Self {
+            s: state_from_seed_sequence(seeds),
+        }
+    }
+}
+
+impl SplittableRng for Xoshiro256Plus {
+    /// Jumps ahead by 2^128 steps.
+    fn jump_substream(&mut self) {
+        self.jump();
+    }
+}
+
+/// 参照実装 (C言語) の出力と一致することを確認するテスト
+#[test]
+fn test_xoshiro256() {
+    let mut star_star = Xoshiro256StarStar::from_state([1_u64, 2_u64, 3_u64, 4_u64]).unwrap();
+    assert_eq!(
+        [(); 4].map(|_| star_star.next_u64()),
+        [
+            11_520_u64,
+            0_u64,
+            1_509_978_240_u64,
+            1_215_971_899_390_074_240_u64
+        ]
+    );
+
+    let mut plus = Xoshiro256Plus::from_state([1_u64, 2_u64, 3_u64, 4_u64]).unwrap();
+    assert_eq!(
+        [(); 4].map(|_| plus.next_u64()),
+        [
+            5_u64,
+            211_106_232_532_999_u64,
+            211_106_635_186_183_u64,
+            9_223_759_065_350_669_058_u64
+        ]
+    );
+}
+
+/// jump と long_jump が参照実装の結果と一致することを確認するテスト
+#[test]
+fn test_xoshiro256_jump() {
+    use crate::SplittableDistribution;
+    let mut star_star = Xoshiro256StarStar::from_state([1_u64, 2_u64, 3_u64, 4_u64]).unwrap();
+    star_star.jump();
+    assert_eq!(
+        star_star.state(),
+        [
+            10_122_426_448_480_695_249_u64,
+            8_079_205_330_032_121_950_u64,
+            7_289_065_458_748_526_725_u64,
+            9_477_464_255_293_849_680_u64
+        ]
+    );
+    assert_eq!(star_star.next_u64(), 13_534_147_089_533_256_664_u64);
+
+    let mut plus = Xoshiro256Plus::from_state([1_u64, 2_u64, 3_u64, 4_u64]).unwrap();
+    plus.long_jump();
+    assert_eq!(
+        plus.state(),
+        [
+            678_511_610_814_637_056_u64,
+            15_850_499_779_492_529_430_u64,
+            6_002_989_639_035_333_134_u64,
+            3_559_352_929_785_830_385_u64
+        ]
+    );
+
+    // 分割すると、親は jump した位置に進む
+    let mut parent = crate::Gamma::<Xoshiro256StarStar>::from_rng_seed(1192_u64);
+    let child = parent.split();
+    assert_ne!(child, parent);
+    let mut rng = Xoshiro256StarStar::new(1192_u64);
+    let original = rng.clone();
+    rng.jump_substream();
+    let mut expected = original;
+    expected.jump();
+    assert_eq!(rng, expected);
+}
+
+/// 全て 0 の状態変数を拒否することを確認するテスト
+#[test]
+fn test_xoshiro256_zero_state() {
+    assert_eq!(
+        Xoshiro256StarStar::from_state([0_u64; 4]),
+        Err(ParameterUpdateError::InvalidState)
+    );
+    assert_eq!(
+        Xoshiro256Plus::from_state([0_u64; 4]),
+        Err(ParameterUpdateError::InvalidState)
+    );
+    assert!(Xoshiro256Plus::from_state([0_u64, 0_u64, 0_u64, 1_u64]).is_ok());
+}
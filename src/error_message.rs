@@ -2,11 +2,53 @@ use std::fmt;
 //use std::io;// 標準エラーのfromを実装する場合に使用する。
 
 /// Enum representing possible errors when updating a parameter.
-#[derive(Debug)]
+///
+/// Every `try_set_params` and `from_state` method returns this type. When an error is returned,
+/// the parameters of the random variable remain unchanged.
+///
+/// # Example
+/// ```
+/// fn configure() -> Result<f64, Box<dyn std::error::Error>> {
+///     let mut normal = rand_simple::Normal::new([1192_u32, 765_u32]);
+///     normal.try_set_params(0_f64, -1_f64)?;
+///     Ok(normal.sample())
+/// }
+/// let error = configure().unwrap_err();
+/// assert_eq!(error.to_string(), "Parameter std must be greater than 0, but -1.0 was given.");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ParameterUpdateError {
     /// Error for invalid range configuration within a closed interval `[min, max]`.
     /// This occurs when the minimum value is greater than the maximum value.
     ParameterRangeErrorF64 { min: f64, max: f64 },
+
+    /// The parameter must be strictly greater than 0.
+    NonPositive { name: &'static str, value: f64 },
+
+    /// The parameter must lie within the closed interval `[min, max]`.
+    OutOfRange {
+        name: &'static str,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+
+    /// The parameter must be a finite number (neither NaN nor infinite).
+    NotFinite { name: &'static str, value: f64 },
+
+    /// The parameter `lower` must be strictly less than the parameter `upper`.
+    InvalidOrdering {
+        lower: &'static str,
+        lower_value: f64,
+        upper: &'static str,
+        upper_value: f64,
+    },
+
+    /// The parameter takes a value that the sampling algorithm does not support.
+    Unsupported { name: &'static str, value: f64 },
+
+    /// The state variables passed to `from_state` are all 0, from which the engine never leaves.
+    InvalidState,
 }
 
 impl fmt::Display for ParameterUpdateError {
@@ -16,6 +58,112 @@ impl fmt::Display for ParameterUpdateError {
                 f,
                 "Minimum value {min:?} must be less than maximum value {max:?}."
             ),
+            Self::NonPositive { name, value } => write!(
+                f,
+                "Parameter {name} must be greater than 0, but {value:?} was given."
+            ),
+            Self::OutOfRange {
+                name,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "Parameter {name} must be within [{min:?}, {max:?}], but {value:?} was given."
+            ),
+            Self::NotFinite { name, value } => write!(
+                f,
+                "Parameter {name} must be a finite number, but {value:?} was given."
+            ),
+            Self::InvalidOrdering {
+                lower,
+                lower_value,
+                upper,
+                upper_value,
+            } => write!(
+                f,
+                "Parameter {lower} ({lower_value:?}) must be less than parameter {upper} ({upper_value:?})."
+            ),
+            Self::Unsupported { name, value } => write!(
+                f,
+                "Parameter {name} does not support the value {value:?}."
+            ),
+            Self::InvalidState => write!(f, "The state variables must not all be 0."),
+        }
+    }
+}
+
+impl std::error::Error for ParameterUpdateError {}
+
+/// Enum representing possible errors when restoring a generator from bytes
+/// written by `to_bytes`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeserializeError {
+    /// The bytes do not start with the identifier of this crate's format.
+    InvalidHeader,
+
+    /// The bytes were written in a format version this crate cannot read.
+    UnsupportedVersion { version: u16 },
+
+    /// The bytes hold a different distribution from the one being restored.
+    DistributionMismatch {
+        expected: &'static str,
+        found: String,
+    },
+
+    /// The bytes end before all parameters and state variables are read.
+    UnexpectedEnd,
+
+    /// Extra bytes remain after all parameters and state variables are read.
+    TrailingBytes { length: usize },
+
+    /// The stored parameters are rejected by `try_set_params`.
+    InvalidParameter(ParameterUpdateError),
+
+    /// The stored state variables of an engine are all 0, as in zeroed or corrupted bytes.
+    InvalidState,
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => {
+                write!(f, "The bytes are not a serialized random number generator.")
+            }
+            Self::UnsupportedVersion { version } => write!(
+                f,
+                "Format version {version} is not supported (supported version: {}).",
+                crate::serialization::FORMAT_VERSION
+            ),
+            Self::DistributionMismatch { expected, found } => write!(
+                f,
+                "Expected a serialized {expected} distribution, but found {found}."
+            ),
+            Self::UnexpectedEnd => write!(f, "The bytes end unexpectedly."),
+            Self::TrailingBytes { length } => {
+                write!(f, "{length} unexpected bytes remain after the data.")
+            }
+            Self::InvalidParameter(error) => write!(f, "Invalid parameter: {error}"),
+            Self::InvalidState => write!(f, "The stored state variables are all 0."),
+        }
+    }
+}
+
+impl std::error::Error for DeserializeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidParameter(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParameterUpdateError> for DeserializeError {
+    fn from(error: ParameterUpdateError) -> Self {
+        match error {
+            // from_state が状態変数を拒否した場合は、パラメータではなく状態変数の誤りとして返す
+            ParameterUpdateError::InvalidState => Self::InvalidState,
+            _ => Self::InvalidParameter(error),
         }
     }
 }
@@ -35,3 +183,51 @@ fn test_parameter_update_error() {
         "Minimum value 11.0 must be less than maximum value -1.0." // Expected output
     );
 }
+
+/// 各分布のパラメータ検証が型付きのエラーを返すことを確認するテスト
+#[test]
+fn test_parameter_update_error_variants() {
+    let mut uniform = crate::Uniform::new(1192_u32);
+    assert_eq!(
+        uniform.try_set_params(1_f64, -1_f64),
+        Err(ParameterUpdateError::InvalidOrdering {
+            lower: "min",
+            lower_value: 1_f64,
+            upper: "max",
+            upper_value: -1_f64,
+        })
+    );
+    assert_eq!(
+        uniform
+            .try_set_params(f64::NAN, 1_f64)
+            .unwrap_err()
+            .to_string(),
+        "Parameter min must be a finite number, but NaN was given."
+    );
+    // エラー時はパラメータが変更されない
+    assert_eq!(format!("{uniform}"), "Range (Closed Interval): [0, 1]");
+
+    let mut bernoulli = crate::Bernoulli::new(1192_u32);
+    assert_eq!(
+        bernoulli.try_set_params(1.5_f64).unwrap_err().to_string(),
+        "Parameter probability must be within [0.0, 1.0], but 1.5 was given."
+    );
+
+    let mut gamma = crate::Gamma::new([1192_u32, 765_u32, 1543_u32]);
+    assert_eq!(
+        gamma.try_set_params(1_f64 / 3_f64, 1_f64),
+        Err(ParameterUpdateError::Unsupported {
+            name: "shape",
+            value: 1_f64 / 3_f64,
+        })
+    );
+
+    let mut chi = crate::Chi::new([1192_u32, 765_u32, 1543_u32, 2003_u32]);
+    assert_eq!(
+        chi.try_set_params(0_u64),
+        Err(ParameterUpdateError::NonPositive {
+            name: "degree_of_freedom",
+            value: 0_f64,
+        })
+    );
+}
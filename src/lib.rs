@@ -6,13 +6,37 @@ mod distributions; // 確率変数の詳細
 mod standard_distributions; // 標準分布を計算するモジュール
                             //#[cfg(test)]
                             //mod test_distributions; // 機能確認のためのテストモジュール
+mod engines; // 疑似乱数生成器 (エンジン)
 mod error_message; // パラメータ更新時のエラーメッセージモジュール
+mod seeding; // 乱数の種の展開
+mod serialization; // 乱数生成器の保存と復元
+mod traits; // 確率分布の共通トレイト
 
 // エラーメッセージ
-pub use crate::error_message::ParameterUpdateError;
+pub use crate::error_message::{DeserializeError, ParameterUpdateError};
+
+// 共通トレイト
+pub use crate::traits::{
+    CoreRng, Distribution, RandomAccessRng, SampleIter, SplittableDistribution, SplittableRng,
+};
+
+// 疑似乱数生成器 (エンジン)
+pub use crate::engines::chacha20::ChaCha20;
+pub use crate::engines::high_precision::HighPrecision;
+pub use crate::engines::mt19937::{Mt19937, Mt19937_64};
+pub use crate::engines::pcg::{Pcg32, Pcg64};
+pub use crate::engines::philox::Philox4x32;
+pub use crate::engines::xorshift160::Xorshift160;
+pub use crate::engines::xoshiro256::{Xoshiro256Plus, Xoshiro256StarStar};
 
 // 共通処理
 
+// Xorshift160の状態変数のジャンプ
+pub use crate::standard_distributions::{xorshift160_jump, xorshift160_jump_pow2};
+
+// 乱数の種の展開
+pub use crate::seeding::{fill_entropy, legacy_state, SeedSequence};
+
 #[macro_export]
 /// Macro to generate an array of random seeds in a `std` environment.
 ///
@@ -59,6 +83,8 @@ pub use crate::error_message::ParameterUpdateError;
 /// # Notes
 /// - The seeds generated are dependent on the system time and provide a level of randomness sufficient for most applications.
 /// - This macro is designed to work in environments where `std` is available, as it relies on `std::time::SystemTime`.
+/// - Processes started within the same millisecond receive nearly identical seeds. Use the `from_entropy()`
+///   constructors, or [`fill_entropy`], when independent runs must not collide.
 macro_rules! generate_seeds {
     ($length: expr) => {{
         // 状態変数を保存する配列を保存する。
@@ -70,9 +96,9 @@ macro_rules! generate_seeds {
         for i in 0..array.len() {
             array[i] = match i % 6_usize {
                 0_usize => (duration.as_millis() & 0xFFFF_FFFF) as u32,
-                1_usize => std::u32::MAX - (duration.as_nanos() & 0xFFFF_FFFF) as u32,
+                1_usize => u32::MAX - (duration.as_nanos() & 0xFFFF_FFFF) as u32,
                 2_usize => ((duration.as_secs() & 0xFFFF_FFFF) as u32) / 60_u32,
-                3_usize => std::u32::MAX - (duration.as_micros() & 0xFFFF_FFFF) as u32,
+                3_usize => u32::MAX - (duration.as_micros() & 0xFFFF_FFFF) as u32,
                 4_usize => (duration.as_secs() & 0xFFFF_FFFF) as u32,
                 5_usize => ((duration.as_millis() & 0xFFFF_FFFF) as u32) / 60_u32,
                 _ => 1_192_765_u32,
@@ -94,6 +120,9 @@ fn test_generate_seeds() {
 /// 乱数の種の配列
 /// # 戻り値
 /// 互いに異なる値に修正した乱数の種の配列
+/// # 注意
+/// 既存の乱数列を再現するために new でのみ使用する。
+/// 1つの乱数の種から全ての状態変数を作成する場合は from_seed (SeedSequence) を使用する。
 macro_rules! adjust_seeds {
     ($array:expr) => {{
         // 値を上書きするために配列のコピーを作成する。
@@ -119,91 +148,105 @@ pub(crate) use adjust_seeds;
 // 連続型確率変数
 
 // 一様乱数
-pub use crate::distributions::uniform::Uniform;
+pub use crate::distributions::uniform::{Uniform, UniformParams};
 
 // 正規分布
-pub use crate::distributions::normal::Normal;
+pub use crate::distributions::normal::{Normal, NormalParams};
 
 // 半正規分布
-pub use crate::distributions::half_normal::HalfNormal;
+pub use crate::distributions::half_normal::{HalfNormal, HalfNormalParams};
 
 // 対数正規分布
-pub use crate::distributions::log_normal::LogNormal;
+pub use crate::distributions::log_normal::{LogNormal, LogNormalParams};
 
 // コーシー分布
-pub use crate::distributions::cauchy::Cauchy;
+pub use crate::distributions::cauchy::{Cauchy, CauchyParams};
 
 // 半コーシー分布
-pub use crate::distributions::half_cauchy::HalfCauchy;
+pub use crate::distributions::half_cauchy::{HalfCauchy, HalfCauchyParams};
 
 // レヴィ分布
-pub use crate::distributions::levy::Levy;
+pub use crate::distributions::levy::{Levy, LevyParams};
 
 // 指数分布
-pub use crate::distributions::exponential::Exponential;
+pub use crate::distributions::exponential::{Exponential, ExponentialParams};
 
 // ラプラス分布
-pub use crate::distributions::laplace::Laplace;
+pub use crate::distributions::laplace::{Laplace, LaplaceParams};
 
 // 対数ラプラス分布
-pub use crate::distributions::log_laplace::LogLaplace;
+pub use crate::distributions::log_laplace::{LogLaplace, LogLaplaceParams};
 
 // レイリー分布
-pub use crate::distributions::rayleigh::Rayleigh;
+pub use crate::distributions::rayleigh::{Rayleigh, RayleighParams};
 
 // ワイブル分布
-pub use crate::distributions::weibull::Weibull;
+pub use crate::distributions::weibull::{Weibull, WeibullParams};
 
 // 反射ワイブル分布
-pub use crate::distributions::reflected_weibull::ReflectedWeibull;
+pub use crate::distributions::reflected_weibull::{ReflectedWeibull, ReflectedWeibullParams};
 
 // フレシェ分布
-pub use crate::distributions::frechet::Frechet;
+pub use crate::distributions::frechet::{Frechet, FrechetParams};
 
 // ガンベル分布
-pub use crate::distributions::gunbel::Gunbel;
+pub use crate::distributions::gunbel::{Gunbel, GunbelParams};
 
 // ガンマ分布
-pub use crate::distributions::gamma::Gamma;
+pub use crate::distributions::gamma::{Gamma, GammaParams};
 
 // ベータ分布
-pub use crate::distributions::beta::Beta;
+pub use crate::distributions::beta::{Beta, BetaParams};
 
 // ディリクレ分布
-//pub struct Dirichlet {}
+pub use crate::distributions::dirichlet::{Dirichlet, DirichletParams};
 
 // べき関数分布
-pub use crate::distributions::power_function::PowerFunction;
+pub use crate::distributions::power_function::{PowerFunction, PowerFunctionParams};
 
 // 指数べき分布
-//pub struct ExponentialPower {}
+pub use crate::distributions::exponential_power::{ExponentialPower, ExponentialPowerParams};
+
+// 半指数べき分布
+pub use crate::distributions::half_exponential_power::{
+    HalfExponentialPower, HalfExponentialPowerParams,
+};
 
 // アーラン分布
-pub use crate::distributions::erlang::Erlang;
+pub use crate::distributions::erlang::{Erlang, ErlangParams};
 
 // χ二乗分布
-pub use crate::distributions::chi_square::ChiSquare;
+pub use crate::distributions::chi_square::{ChiSquare, ChiSquareParams};
 
 // χ分布
-pub use crate::distributions::chi::Chi;
+pub use crate::distributions::chi::{Chi, ChiParams};
 
 // F分布
-pub use crate::distributions::f::FDistribution;
+pub use crate::distributions::f::{FDistribution, FDistributionParams};
 
 // t分布
-pub use crate::distributions::t::TDistribution;
+pub use crate::distributions::t::{TDistribution, TDistributionParams};
 
 // 逆ガウス分布
-pub use crate::distributions::inverse_gaussian::InverseGaussian;
+pub use crate::distributions::inverse_gaussian::{InverseGaussian, InverseGaussianParams};
 
 // 三角分布
-pub use crate::distributions::triangular::Triangular;
+pub use crate::distributions::triangular::{Triangular, TriangularParams};
 
 // パレート分布
-//pub struct Pareto {}
+pub use crate::distributions::pareto::{Pareto, ParetoParams};
+
+// ロマックス分布
+pub use crate::distributions::lomax::{Lomax, LomaxParams};
+
+// 一般化パレート分布
+pub use crate::distributions::generalized_pareto::{GeneralizedPareto, GeneralizedParetoParams};
 
 // ロジスティック分布
-//pub struct Logistic {}
+pub use crate::distributions::logistic::{Logistic, LogisticParams};
+
+// 対数ロジスティック分布
+pub use crate::distributions::log_logistic::{LogLogistic, LogLogisticParams};
 
 // 双曲線正割分布
 //pub struct HeyperbolicSecant {}
@@ -240,14 +283,17 @@ pub use crate::distributions::triangular::Triangular;
 
 // 離散型確率変数
 
+// 整数の一様分布
+pub use crate::distributions::uniform_int::{UniformInt, UniformIntParams, UniformIntType};
+
 // ベルヌーイ分布
-pub use crate::distributions::bernoulli::Bernoulli;
+pub use crate::distributions::bernoulli::{Bernoulli, BernoulliParams};
 
 // 二項分布
 //pub  struct Binomial {}
 
 // 幾何分布
-pub use crate::distributions::geometric::Geometric;
+pub use crate::distributions::geometric::{Geometric, GeometricParams};
 
 // ポアソン分布
 //pub struct Poisson {}
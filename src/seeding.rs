@@ -0,0 +1,303 @@
+use crate::standard_distributions::{generate_random_state, xorshift160};
+use std::io::Read;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// OS のエントロピー源
+const ENTROPY_DEVICE: &str = "/dev/urandom";
+
+/// 代替のエントロピーを呼び出しごとに変えるためのカウンター
+static ENTROPY_COUNTER: AtomicU64 = AtomicU64::new(0_u64);
+
+/// SplitMix64 の加算定数 (黄金比)
+const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15_u64;
+
+/// 展開した状態変数を使い始める前に読み捨てる Xorshift160 の乱数の個数
+const WARM_UP_XORSHIFT160: usize = 32_usize;
+
+/// SplitMix64 の出力関数 (64 bit の値を撹拌する)
+#[inline]
+fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9_u64);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB_u64);
+    z ^ (z >> 31)
+}
+
+/// Seed expansion based on SplitMix64.
+///
+/// `generate_random_state` of the earlier versions only writes the seed into the last of the five
+/// Xorshift160 state words, so nearby seeds produce similar early outputs. `SeedSequence` expands a
+/// `u32`, `u64` or byte-slice seed with SplitMix64 so that all 160 bits of every state depend on
+/// the seed, and discards the first outputs of each state as a warm-up.
+///
+/// Repeated calls of [`SeedSequence::next_state`] return a sequence of states derived from the same
+/// seed, which can be used for the internal streams of multi-stream distributions.
+///
+/// The constructors `new(seed)` of each distribution keep the legacy scheme (see [`legacy_state`]),
+/// so existing results remain reproducible.
+///
+/// # Example
+/// ```
+/// use rand_simple::{SeedSequence, Uniform, UniformParams};
+///
+/// let mut seeds = SeedSequence::from_u64(1192_u64);
+/// let params = UniformParams { min: 0_f64, max: 1_f64 };
+/// let mut uniform = Uniform::from_state(seeds.next_state(), params).unwrap();
+/// println!("Returns a random number -> {}", uniform.sample());
+///
+/// // The same seed always yields the same states
+/// let mut again = SeedSequence::from_u64(1192_u64);
+/// assert_eq!(again.next_state(), SeedSequence::from_u64(1192_u64).next_state());
+///
+/// // Multi-stream distributions take one state per internal stream
+/// let state = [again.next_state(), again.next_state()];
+/// let params = rand_simple::NormalParams { mean: 0_f64, std: 1_f64 };
+/// let mut normal = rand_simple::Normal::from_state(state, params).unwrap();
+/// println!("Returns a random number -> {}", normal.sample());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedSequence {
+    state: u64, // SplitMix64 の状態変数
+}
+
+impl SeedSequence {
+    /// Create a seed sequence from a 32 bit seed.
+    pub fn from_u32(seed: u32) -> Self {
+        Self::from_u64(u64::from(seed))
+    }
+
+    /// Create a seed sequence from a 64 bit seed.
+    pub fn from_u64(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Create a seed sequence from a byte slice of any length.
+    /// Every byte, and the length of the slice, affect the generated states.
+    pub fn from_bytes(seed: &[u8]) -> Self {
+        // 8 bytes ずつ読み込み、SplitMix64 の出力関数で撹拌しながら吸収する
+        let mut state: u64 = mix64(seed.len() as u64 ^ GOLDEN_GAMMA);
+        for chunk in seed.chunks(8_usize) {
+            let mut word = [0_u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            state = mix64(state ^ u64::from_le_bytes(word)).wrapping_add(GOLDEN_GAMMA);
+        }
+        Self { state }
+    }
+
+    /// Create a seed sequence from 256 bits of entropy collected by [`fill_entropy`].
+    /// Each call yields a different, non-reproducible sequence.
+    pub fn from_entropy() -> Self {
+        let mut seed = [0_u8; 32];
+        fill_entropy(&mut seed);
+        Self::from_bytes(&seed)
+    }
+
+    /// Return the next 64 bit output of SplitMix64.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GOLDEN_GAMMA);
+        mix64(self.state)
+    }
+
+    /// Return the next Xorshift160 state `(x, y, z, u, v)`.
+    /// All five words are filled from the seed, the all-zero state is never returned,
+    /// and the first outputs of the state are discarded as a warm-up.
+    pub fn next_state(&mut self) -> [u32; 5] {
+        loop {
+            let words: [u64; 3] = [self.next_u64(), self.next_u64(), self.next_u64()];
+            let mut xyzuv: [u32; 5] = [
+                words[0] as u32,
+                (words[0] >> 32) as u32,
+                words[1] as u32,
+                (words[1] >> 32) as u32,
+                words[2] as u32,
+            ];
+            // 全て 0 の状態変数は 0 しか生成しないため、引き直す
+            if xyzuv == [0_u32; 5] {
+                continue;
+            }
+            for _ in 0..WARM_UP_XORSHIFT160 {
+                let _ = xorshift160(&mut xyzuv);
+            }
+            return xyzuv;
+        }
+    }
+}
+
+/// Fill `buffer` with entropy from the operating system.
+///
+/// Reads `/dev/urandom`. Where it is unavailable, falls back to mixing the current time, the process
+/// id, the addresses of a stack and a heap variable, and a process-wide call counter with SplitMix64,
+/// so that two processes started in the same millisecond, or two calls in the same process, still
+/// receive different bytes.
+///
+/// # Example
+/// ```
+/// let mut seed = [0_u8; 16];
+/// rand_simple::fill_entropy(&mut seed);
+/// println!("Returns entropy -> {seed:?}");
+/// ```
+pub fn fill_entropy(buffer: &mut [u8]) {
+    let read = std::fs::File::open(ENTROPY_DEVICE).and_then(|mut file| file.read_exact(buffer));
+    if read.is_err() {
+        fallback_entropy(buffer);
+    }
+}
+
+/// OS のエントロピー源を利用できない場合に、時刻・プロセス ID・アドレス・カウンターを撹拌して代替する
+fn fallback_entropy(buffer: &mut [u8]) {
+    let duration = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let stack: u8 = 0_u8;
+    let heap: Box<u8> = Box::new(0_u8);
+    let mut seeds = SeedSequence::from_u64(duration.as_nanos() as u64);
+    for source in [
+        (duration.as_nanos() >> 64) as u64,
+        u64::from(std::process::id()),
+        &stack as *const u8 as u64,
+        &*heap as *const u8 as u64,
+        ENTROPY_COUNTER.fetch_add(1_u64, Ordering::Relaxed),
+    ] {
+        seeds.state = mix64(seeds.next_u64() ^ source);
+    }
+    for chunk in buffer.chunks_mut(8_usize) {
+        let bytes = seeds.next_u64().to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+/// Legacy seeding scheme of the constructors `new(seed)`.
+///
+/// Returns `[123456789, 362436069, 521288629, 88675123, seed]`, the state used by Marsaglia (2003),
+/// with the seed written into the last word. Kept so that results generated with earlier versions
+/// can be reproduced.
+///
+/// # Example
+/// ```
+/// use rand_simple::{legacy_state, Uniform, UniformParams};
+///
+/// let params = UniformParams { min: 0_f64, max: 1_f64 };
+/// let mut legacy = Uniform::from_state(legacy_state(1192_u32), params).unwrap();
+/// let mut uniform = Uniform::new(1192_u32);
+/// assert_eq!(legacy.sample(), uniform.sample());
+/// ```
+pub fn legacy_state(seed: u32) -> [u32; 5] {
+    generate_random_state(seed)
+}
+
+/// SplitMix64 の出力が参照実装と一致することを確認するテスト
+#[test]
+fn test_split_mix64() {
+    // 参照実装 (https://prng.di.unimi.it/splitmix64.c) で乱数の種 1234567 の場合の出力
+    let mut seeds = SeedSequence::from_u64(1_234_567_u64);
+    assert_eq!(seeds.next_u64(), 6_457_827_717_110_365_317_u64);
+    assert_eq!(seeds.next_u64(), 3_203_168_211_198_807_973_u64);
+    assert_eq!(seeds.next_u64(), 9_817_491_932_198_370_423_u64);
+}
+
+/// 近い乱数の種から作成した状態変数が大きく異なることを確認するテスト
+#[test]
+fn test_seed_expansion() {
+    // 状態変数の全ての語が乱数の種に依存する
+    let xyzuv_0: [u32; 5] = SeedSequence::from_u32(1192_u32).next_state();
+    let xyzuv_1: [u32; 5] = SeedSequence::from_u32(1193_u32).next_state();
+    for (word_0, word_1) in xyzuv_0.iter().zip(xyzuv_1.iter()) {
+        assert_ne!(word_0, word_1);
+    }
+
+    // 最初の乱数の異なるビットの数がおおよそ半分になる
+    let mut different_bits: u32 = 0_u32;
+    for seed in 0_u32..100_u32 {
+        let mut xyzuv_a: [u32; 5] = SeedSequence::from_u32(seed).next_state();
+        let mut xyzuv_b: [u32; 5] = SeedSequence::from_u32(seed + 1_u32).next_state();
+        different_bits += (xorshift160(&mut xyzuv_a) ^ xorshift160(&mut xyzuv_b)).count_ones();
+    }
+    assert!((1_200_u32..2_000_u32).contains(&different_bits));
+
+    // 同じ乱数の種からは同じ状態変数が作成され、続けて作成した状態変数は異なる
+    let mut seeds = SeedSequence::from_bytes(b"rand_simple");
+    let first: [u32; 5] = seeds.next_state();
+    assert_eq!(SeedSequence::from_bytes(b"rand_simple").next_state(), first);
+    assert_ne!(seeds.next_state(), first);
+    assert_ne!(SeedSequence::from_bytes(b"rand_simplf").next_state(), first);
+    assert_ne!(
+        SeedSequence::from_bytes(b"rand_simple\0").next_state(),
+        first
+    );
+}
+
+/// エントロピーによる乱数の種が呼び出しごとに異なることを確認するテスト
+#[test]
+fn test_entropy() {
+    let mut first = [0_u8; 32];
+    let mut second = [0_u8; 32];
+    fill_entropy(&mut first);
+    fill_entropy(&mut second);
+    assert_ne!(first, second);
+    assert_ne!(
+        SeedSequence::from_entropy().next_state(),
+        SeedSequence::from_entropy().next_state()
+    );
+
+    // 代替のエントロピーも呼び出しごとに異なり、端数の長さも埋める
+    let mut first = [0_u8; 13];
+    let mut second = [0_u8; 13];
+    fallback_entropy(&mut first);
+    fallback_entropy(&mut second);
+    assert_ne!(first, second);
+    assert_ne!(first[8..], [0_u8; 5]);
+}
+
+/// 従来の状態変数が変わっていないことを確認するテスト
+#[test]
+fn test_legacy_state() {
+    assert_eq!(
+        legacy_state(1192_u32),
+        [123456789, 362436069, 521288629, 88675123, 1192]
+    );
+}
+
+/// 乱数の種の列から作成した乱数生成器が再現可能で、互いに異なることを確認するテスト
+#[test]
+fn test_from_seed_sequence() {
+    let mut seeds = SeedSequence::from_u64(1192_u64);
+    let mut first = crate::Beta::from_seed_sequence(&mut seeds);
+    let mut second = crate::Beta::from_seed_sequence(&mut seeds);
+    let mut replay = crate::Beta::from_seed_sequence(&mut SeedSequence::from_u64(1192_u64));
+    assert_eq!(
+        first.params(),
+        crate::Beta::new([1_u32, 2, 3, 4, 5, 6]).params()
+    );
+    for _ in 0..100 {
+        let x = first.sample();
+        assert_eq!(x, replay.sample());
+        assert_ne!(x, second.sample());
+    }
+
+    let mut uniform = crate::Uniform::from_entropy();
+    assert!((0_f64..=1_f64).contains(&uniform.sample()));
+    assert_ne!(
+        crate::Uniform::from_entropy().state(),
+        crate::Uniform::from_entropy().state()
+    );
+}
+
+/// 1つの乱数の種から作成した複数の状態変数が互いに異なることを確認するテスト
+#[test]
+fn test_from_seed() {
+    for seed in [0_u64, 1_u64, 1192_u64, u64::MAX] {
+        let state = crate::FDistribution::from_seed(seed).state();
+        for i in 0..state.len() {
+            for j in (i + 1)..state.len() {
+                assert_ne!(state[i], state[j]);
+            }
+        }
+    }
+    assert_eq!(
+        crate::Normal::from_seed(1192_u64),
+        crate::Normal::from_seed_sequence(&mut SeedSequence::from_u64(1192_u64))
+    );
+    assert_ne!(
+        crate::Normal::from_seed(1192_u64),
+        crate::Normal::from_seed(1193_u64)
+    );
+}
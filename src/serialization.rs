@@ -0,0 +1,302 @@
+// 乱数生成器をバイト列へ保存・復元するための共通処理
+//
+// バイト列の構成 (数値は全てリトルエンディアン)
+// | 識別子 "RSMP" (4 bytes) | 形式のバージョン u16 | 分布名の長さ u8 | 分布名 (UTF-8) | パラメータ | 状態変数 |
+// * パラメータ: f64 はビット表現、u64 と i64 はそのまま 8 bytes で保存する (可変長の場合は要素数 u64 を先頭に置く)
+// * 状態変数: Xorshift160 の (x, y, z, u, v) を u32 で保存する (全て 0 の状態変数は復元しない)
+use crate::DeserializeError;
+
+/// バイト列の先頭に置く識別子
+const MAGIC: [u8; 4] = *b"RSMP";
+
+/// バイト列の形式のバージョン
+pub(crate) const FORMAT_VERSION: u16 = 1_u16;
+
+/// バイト列へ保存できる値
+pub(crate) trait Field: Sized {
+    /// 値をバイト列の末尾に追加する
+    fn encode(&self, bytes: &mut Vec<u8>);
+    /// バイト列から値を読み出す
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError>;
+}
+
+impl Field for f64 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_bits().to_le_bytes());
+    }
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        Ok(f64::from_bits(u64::decode(decoder)?))
+    }
+}
+
+impl Field for u64 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes());
+    }
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        Ok(u64::from_le_bytes(decoder.take()?))
+    }
+}
+
+impl Field for i64 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes());
+    }
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        Ok(i64::from_le_bytes(decoder.take()?))
+    }
+}
+
+impl Field for u32 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes());
+    }
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        Ok(u32::from_le_bytes(decoder.take()?))
+    }
+}
+
+// 状態変数 [u32; 5] や、複数の状態変数 [[u32; 5]; N] を保存する
+impl<T: Field, const N: usize> Field for [T; N] {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        for value in self.iter() {
+            value.encode(bytes);
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let mut values = Vec::with_capacity(N);
+        for _ in 0..N {
+            values.push(T::decode(decoder)?);
+        }
+        // 要素数は N で確定しているため、変換は失敗しない
+        Ok(values
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("the number of elements is N")))
+    }
+}
+
+// 可変長のパラメータ (ディリクレ分布の α など) を、要素数 u64 に続けて保存する
+impl<T: Field> Field for Vec<T> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        (self.len() as u64).encode(bytes);
+        for value in self.iter() {
+            value.encode(bytes);
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let length: u64 = decoder.read()?;
+        // 壊れたバイト列の要素数で領域を確保しないように、1 つずつ読み出す
+        let mut values = Vec::new();
+        for _ in 0..length {
+            values.push(T::decode(decoder)?);
+        }
+        Ok(values)
+    }
+}
+
+/// 分布の名前とパラメータ、状態変数をバイト列に書き出す
+pub(crate) struct Encoder {
+    bytes: Vec<u8>, // 書き出したバイト列
+}
+
+impl Encoder {
+    /// ヘッダー (識別子、バージョン、分布名) を書き出す
+    pub(crate) fn new(distribution: &'static str) -> Self {
+        let mut bytes = Vec::with_capacity(64);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.push(distribution.len() as u8);
+        bytes.extend_from_slice(distribution.as_bytes());
+        Self { bytes }
+    }
+
+    /// 値を書き出す
+    pub(crate) fn write<T: Field>(&mut self, value: &T) {
+        value.encode(&mut self.bytes);
+    }
+
+    /// 書き出したバイト列を返す
+    pub(crate) fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// バイト列からパラメータと状態変数を読み出す
+pub(crate) struct Decoder<'a> {
+    bytes: &'a [u8], // 未読のバイト列
+}
+
+impl<'a> Decoder<'a> {
+    /// ヘッダーを読み出して、識別子、バージョン、分布名を確認する
+    pub(crate) fn new(
+        bytes: &'a [u8],
+        distribution: &'static str,
+    ) -> Result<Self, DeserializeError> {
+        let mut decoder = Self { bytes };
+        if decoder.take::<4>()? != MAGIC {
+            return Err(DeserializeError::InvalidHeader);
+        }
+        let version = u16::from_le_bytes(decoder.take()?);
+        if version != FORMAT_VERSION {
+            return Err(DeserializeError::UnsupportedVersion { version });
+        }
+        let [length] = decoder.take::<1>()?;
+        let found = decoder.take_slice(length as usize)?;
+        if found != distribution.as_bytes() {
+            return Err(DeserializeError::DistributionMismatch {
+                expected: distribution,
+                found: String::from_utf8_lossy(found).into_owned(),
+            });
+        }
+        Ok(decoder)
+    }
+
+    /// 値を読み出す
+    pub(crate) fn read<T: Field>(&mut self) -> Result<T, DeserializeError> {
+        T::decode(self)
+    }
+
+    /// 全てのバイト列を読み終えたことを確認する
+    pub(crate) fn finish(self) -> Result<(), DeserializeError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(DeserializeError::TrailingBytes {
+                length: self.bytes.len(),
+            })
+        }
+    }
+
+    /// 先頭から N bytes 読み出す
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], DeserializeError> {
+        let mut array = [0_u8; N];
+        array.copy_from_slice(self.take_slice(N)?);
+        Ok(array)
+    }
+
+    /// 先頭から length bytes 読み出す
+    fn take_slice(&mut self, length: usize) -> Result<&'a [u8], DeserializeError> {
+        if self.bytes.len() < length {
+            return Err(DeserializeError::UnexpectedEnd);
+        }
+        let (head, tail) = self.bytes.split_at(length);
+        self.bytes = tail;
+        Ok(head)
+    }
+}
+
+/// 保存と復元で同じ乱数列が得られることを確認するテスト
+#[test]
+fn test_round_trip() {
+    let mut beta = crate::Beta::try_new(
+        [1192_u32, 765_u32, 1543_u32, 2003_u32, 1867_u32, 1688_u32],
+        2_f64,
+        1.5_f64,
+    )
+    .unwrap();
+    let _ = beta.sample();
+    let bytes = beta.to_bytes();
+    let mut restored = crate::Beta::from_bytes(&bytes).unwrap();
+    assert_eq!(restored, beta);
+    for _ in 0..100 {
+        assert_eq!(restored.sample(), beta.sample());
+    }
+
+    let erlang = crate::Erlang::try_new([1192_u32, 765_u32, 1543_u32], 3_i64, 0.5_f64).unwrap();
+    let restored = crate::Erlang::from_bytes(&erlang.to_bytes()).unwrap();
+    assert_eq!(restored, erlang);
+
+    // 可変長のパラメータも復元できる
+    let dirichlet =
+        crate::Dirichlet::try_new([1192_u32, 765_u32, 1543_u32], &[0.5_f64, 1_f64, 2_f64]).unwrap();
+    let restored = crate::Dirichlet::from_bytes(&dirichlet.to_bytes()).unwrap();
+    assert_eq!(restored, dirichlet);
+
+    // 整数の型が異なるバイト列は復元しない
+    let uniform_int = crate::UniformInt::<i64>::try_new(1192_u32, -3_i64, 5_i64).unwrap();
+    let bytes = uniform_int.to_bytes();
+    assert_eq!(
+        crate::UniformInt::<i64>::from_bytes(&bytes),
+        Ok(uniform_int)
+    );
+    assert!(crate::UniformInt::<u64>::from_bytes(&bytes).is_err());
+}
+
+/// 不正なバイト列を検出できることを確認するテスト
+#[test]
+fn test_invalid_bytes() {
+    let bytes = crate::Normal::new([1192_u32, 765_u32]).to_bytes();
+
+    // 分布の種類が異なる
+    assert_eq!(
+        crate::LogNormal::from_bytes(&bytes),
+        Err(DeserializeError::DistributionMismatch {
+            expected: "LogNormal",
+            found: String::from("Normal"),
+        })
+    );
+
+    // 途中で途切れている
+    assert_eq!(
+        crate::Normal::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(DeserializeError::UnexpectedEnd)
+    );
+
+    // 余分なバイトがある
+    let mut longer = bytes.clone();
+    longer.push(0_u8);
+    assert_eq!(
+        crate::Normal::from_bytes(&longer),
+        Err(DeserializeError::TrailingBytes { length: 1 })
+    );
+
+    // 識別子が異なる
+    let mut broken = bytes.clone();
+    broken[0] = b'X';
+    assert_eq!(
+        crate::Normal::from_bytes(&broken),
+        Err(DeserializeError::InvalidHeader)
+    );
+
+    // バージョンが異なる
+    let mut future = bytes.clone();
+    future[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+    assert_eq!(
+        crate::Normal::from_bytes(&future),
+        Err(DeserializeError::UnsupportedVersion {
+            version: FORMAT_VERSION + 1
+        })
+    );
+
+    // パラメータが不正
+    let mut encoder = Encoder::new("Normal");
+    encoder.write(&0_f64);
+    encoder.write(&-1_f64);
+    encoder.write(&[[1_u32; 5]; 2]);
+    assert_eq!(
+        crate::Normal::from_bytes(&encoder.finish()),
+        Err(DeserializeError::InvalidParameter(
+            crate::ParameterUpdateError::NonPositive {
+                name: "std",
+                value: -1_f64,
+            }
+        ))
+    );
+
+    // 状態変数が全て 0 (0 で埋められたバイト列など)
+    let mut encoder = Encoder::new("Normal");
+    encoder.write(&0_f64);
+    encoder.write(&1_f64);
+    encoder.write(&[[1_u32; 5], [0_u32; 5]]);
+    assert_eq!(
+        crate::Normal::from_bytes(&encoder.finish()),
+        Err(DeserializeError::InvalidState)
+    );
+    let mut zeroed = crate::Laplace::new(1192_u32).to_bytes();
+    let length = zeroed.len();
+    zeroed[length - 20..].fill(0_u8);
+    assert_eq!(
+        crate::Laplace::from_bytes(&zeroed),
+        Err(DeserializeError::InvalidState)
+    );
+}
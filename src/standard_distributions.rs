@@ -1,3 +1,5 @@
+use crate::CoreRng;
+
 /// Xorshift160の状態変数を作成する。
 /// # 引数
 /// ```_seed: u32```: 乱数の種
@@ -75,39 +77,319 @@ fn test_xorshift160() {
     );
 }
 
-/// 閉区間 ```[0, 1]```の一様乱数
+/// Xorshift160の特性多項式 p(x) の x^0 〜 x^159 の係数 (x^160 の係数 1 は省略する)\
+/// 出力列に Berlekamp-Massey 法を適用して求めた最小多項式 (次数 160) である。
+const CHARACTERISTIC_POLYNOMIAL_XORSHIFT160: [u32; 5] = [
+    0x633F_0001,
+    0xFE89_D38F,
+    0x0060_4345,
+    0x000F_F30F,
+    0x0000_0001,
+];
+
+/// 多項式 a(x) に x を掛けて p(x) で割った余りを計算する
+#[inline]
+fn multiply_by_x_mod(a: &mut [u32; 5]) {
+    let carry: u32 = a[4] >> 31;
+    for i in (1..5).rev() {
+        a[i] = (a[i] << 1) | (a[i - 1] >> 31);
+    }
+    a[0] <<= 1;
+    if carry == 1 {
+        for (a_i, p_i) in a
+            .iter_mut()
+            .zip(CHARACTERISTIC_POLYNOMIAL_XORSHIFT160.iter())
+        {
+            *a_i ^= p_i;
+        }
+    }
+}
+
+/// 多項式の積 a(x) b(x) を p(x) で割った余りを計算する
+fn multiply_mod(a: &[u32; 5], b: &[u32; 5]) -> [u32; 5] {
+    let mut shifted: [u32; 5] = *a;
+    let mut product: [u32; 5] = [0_u32; 5];
+    for i in 0..160 {
+        if (b[i / 32] >> (i % 32)) & 1 == 1 {
+            for (p_j, s_j) in product.iter_mut().zip(shifted.iter()) {
+                *p_j ^= s_j;
+            }
+        }
+        multiply_by_x_mod(&mut shifted);
+    }
+    product
+}
+
+/// 多項式 r(x) に状態遷移を代入した r(T) を状態変数に作用させる (Horner法)
+fn apply_polynomial(xyzuv: &mut [u32; 5], r: &[u32; 5]) {
+    let mut accumulator: [u32; 5] = [0_u32; 5];
+    for i in (0..160).rev() {
+        // 状態遷移は線形なので、Xorshift160の1ステップで T を掛けられる
+        let _ = xorshift160(&mut accumulator);
+        if (r[i / 32] >> (i % 32)) & 1 == 1 {
+            for (a_j, s_j) in accumulator.iter_mut().zip(xyzuv.iter()) {
+                *a_j ^= s_j;
+            }
+        }
+    }
+    *xyzuv = accumulator;
+}
+
+/// Advance the Xorshift160 state `(x, y, z, u, v)` by `steps` steps at once.
+///
+/// The result is identical to calling the generator `steps` times, but only costs
+/// O(log(steps)) polynomial multiplications: `x^steps mod p(x)` is evaluated with the
+/// characteristic polynomial `p(x)` of the linear recurrence and applied to the state.
+///
+/// # Example
+/// ```
+/// let mut uniform = rand_simple::Uniform::new(1192_u32);
+/// let mut state = uniform.state();
+/// rand_simple::xorshift160_jump(&mut state, 1_000_u128);
+///
+/// // Drawing 1,000 values reaches the same state
+/// for _ in 0..1_000 {
+///     let _ = uniform.sample();
+/// }
+/// assert_eq!(uniform.state(), state);
+/// ```
+pub fn xorshift160_jump(xyzuv: &mut [u32; 5], steps: u128) {
+    // x^steps mod p(x) を繰り返し二乗法で計算する
+    let mut power: [u32; 5] = [2_u32, 0_u32, 0_u32, 0_u32, 0_u32]; // x
+    let mut r: [u32; 5] = [1_u32, 0_u32, 0_u32, 0_u32, 0_u32]; // 1
+    let mut n: u128 = steps;
+    while n > 0_u128 {
+        if n & 1_u128 == 1_u128 {
+            r = multiply_mod(&r, &power);
+        }
+        power = multiply_mod(&power, &power);
+        n >>= 1_u32;
+    }
+    apply_polynomial(xyzuv, &r);
+}
+
+/// Advance the Xorshift160 state `(x, y, z, u, v)` by `2^exponent` steps at once.
+///
+/// Unlike [`xorshift160_jump`], the exponent is not limited to 127, so substreams separated by
+/// up to `2^159` steps can be carved out of the period `2^160 - 1` of a single seed.
+///
+/// # Example
+/// ```
+/// // Give each worker a substream that starts 2^100 steps after the previous one
+/// let mut state = rand_simple::Uniform::new(1192_u32).state();
+/// let mut worker_states = Vec::new();
+/// for _ in 0..4 {
+///     worker_states.push(state);
+///     rand_simple::xorshift160_jump_pow2(&mut state, 100_u32);
+/// }
+/// ```
+pub fn xorshift160_jump_pow2(xyzuv: &mut [u32; 5], exponent: u32) {
+    // x^(2^exponent) mod p(x) を exponent 回の二乗で計算する
+    let mut r: [u32; 5] = [2_u32, 0_u32, 0_u32, 0_u32, 0_u32]; // x
+    for _ in 0..exponent {
+        r = multiply_mod(&r, &r);
+    }
+    apply_polynomial(xyzuv, &r);
+}
+
+/// 乱数生成器を分割するときに、親の状態変数を進めるステップ数の指数 (2^100 ステップ)\
+/// 周期 2^160 - 1 の中で 2^59 回まで分割しても、部分列は重ならない。
+pub(crate) const SPLIT_JUMP_EXPONENT: u32 = 100_u32;
+
+/// ジャンプ後の状態変数が逐次計算の結果と一致することを確認するテスト
+#[test]
+fn test_xorshift160_jump() {
+    let seed: u32 = 1192;
+    let initial: [u32; 5] = generate_random_state(seed);
+
+    // 逐次計算と比較する
+    let mut sequential: [u32; 5] = initial;
+    for steps in 0_u128..=1_100_u128 {
+        if steps % 100_u128 == 0_u128 {
+            let mut jumped: [u32; 5] = initial;
+            xorshift160_jump(&mut jumped, steps);
+            assert_eq!(jumped, sequential);
+        }
+        let _ = xorshift160(&mut sequential);
+    }
+
+    // 2の冪のジャンプと比較する
+    let mut jumped: [u32; 5] = initial;
+    xorshift160_jump(&mut jumped, 1_u128 << 10);
+    let mut jumped_pow2: [u32; 5] = initial;
+    xorshift160_jump_pow2(&mut jumped_pow2, 10_u32);
+    assert_eq!(jumped, jumped_pow2);
+
+    // ジャンプの合成: 2^100 + 2^100 = 2^101
+    let mut twice: [u32; 5] = initial;
+    xorshift160_jump_pow2(&mut twice, 100_u32);
+    xorshift160_jump(&mut twice, 1_u128 << 100);
+    let mut once: [u32; 5] = initial;
+    xorshift160_jump_pow2(&mut once, 101_u32);
+    assert_eq!(twice, once);
+    assert_eq!(
+        once,
+        [
+            3_587_912_458,
+            515_105_370,
+            487_952_841,
+            3_235_193_515,
+            2_205_084_682
+        ]
+    );
+}
+
+/// 乱数生成器の分割で、子が親の状態を引き継ぎ、親がジャンプすることを確認するテスト
+#[test]
+fn test_split() {
+    use crate::SplittableDistribution;
+    let mut parent = crate::Gamma::new([1192_u32, 765_u32, 1543_u32]);
+    let original = parent.clone();
+
+    // 子は分割前の親と同じ乱数列を生成する
+    let child = parent.split();
+    assert_eq!(child, original);
+
+    // 親の状態変数は全て 2^100 ステップ進む
+    let mut expected: [[u32; 5]; 3] = original.state();
+    for xyzuv in expected.iter_mut() {
+        xorshift160_jump_pow2(xyzuv, SPLIT_JUMP_EXPONENT);
+    }
+    assert_eq!(parent.state(), expected);
+
+    // fork で得られる子は互いに異なる状態変数を持つ
+    let children = parent.fork(4_usize);
+    for i in 0..children.len() {
+        for j in (i + 1)..children.len() {
+            assert_ne!(children[i].state(), children[j].state());
+        }
+    }
+}
+
+/// 閉区間 ```[0, 1]```の一様乱数\
+/// 53 bit の場合はエンジンの next_f64_53 による [0, 1) の値を返す (1 との差 2^-53 は区別しない)
 #[inline]
-pub(crate) fn xorshift160_0_to_1(xyzuv: &mut [u32; 5]) -> f64 {
-    f64::from(xorshift160(xyzuv)) / MAX_U32_AS_F64
+pub(crate) fn uniform_0_to_1<R: CoreRng>(rng: &mut R) -> f64 {
+    if R::HIGH_PRECISION {
+        rng.next_f64_53()
+    } else {
+        f64::from(rng.next_u32()) / MAX_U32_AS_F64
+    }
 }
 
 /// 区間[0, 1)の一様乱数
 #[inline]
-pub(crate) fn xorshift160_0_or_greater_and_less_than_1(xyzuv: &mut [u32; 5]) -> f64 {
+pub(crate) fn uniform_0_or_greater_and_less_than_1<R: CoreRng>(rng: &mut R) -> f64 {
+    if R::HIGH_PRECISION {
+        // 32 bit のエンジンでは genrand_res53 と同じ値になる
+        return rng.next_f64_53();
+    }
     loop {
-        _ = xorshift160(xyzuv);
-        if xyzuv[4] != u32::MAX {
-            return f64::from(xyzuv[4]) / MAX_U32_AS_F64;
+        let u: u32 = rng.next_u32();
+        if u != u32::MAX {
+            return f64::from(u) / MAX_U32_AS_F64;
         }
     }
 }
 
 /// 開区間(0, 1)の一様乱数
 #[inline]
-pub(crate) fn xorshift160_greater_than_0_and_less_than_1(xyzuv: &mut [u32; 5]) -> f64 {
+pub(crate) fn uniform_greater_than_0_and_less_than_1<R: CoreRng>(rng: &mut R) -> f64 {
+    if R::HIGH_PRECISION {
+        loop {
+            let u: f64 = rng.next_f64_53();
+            if u != 0_f64 {
+                return u;
+            }
+        }
+    }
     loop {
-        _ = xorshift160(xyzuv);
-        if xyzuv[4] != 0_u32 && xyzuv[4] != u32::MAX {
-            return f64::from(xyzuv[4]) / MAX_U32_AS_F64;
+        let u: u32 = rng.next_u32();
+        if u != 0_u32 && u != u32::MAX {
+            return f64::from(u) / MAX_U32_AS_F64;
         }
     }
 }
 
-/// Xorshift160の基本計算を[0, 1]の一様乱数に変換するための分母
+/// 閉区間 ```[0, range]```の整数の一様乱数
+/// Lemire (2019) の乗算とシフトによる方法で、剰余による偏りなく生成する
+#[inline]
+pub(crate) fn uniform_integer_0_to<R: CoreRng>(rng: &mut R, range: u64) -> u64 {
+    if range < u64::from(u32::MAX) {
+        // 32 bit の出力 1 つで足りる場合
+        let n: u32 = range as u32 + 1_u32;
+        let mut m: u64 = u64::from(rng.next_u32()) * u64::from(n);
+        if (m as u32) < n {
+            // 2^32 を n で割った余りだけ下位の値を棄却すると、偏りがなくなる
+            let threshold: u32 = n.wrapping_neg() % n;
+            while (m as u32) < threshold {
+                m = u64::from(rng.next_u32()) * u64::from(n);
+            }
+        }
+        m >> 32
+    } else if range == u64::from(u32::MAX) {
+        u64::from(rng.next_u32())
+    } else if range < u64::MAX {
+        let n: u64 = range + 1_u64;
+        let mut m: u128 = u128::from(rng.next_u64()) * u128::from(n);
+        if (m as u64) < n {
+            let threshold: u64 = n.wrapping_neg() % n;
+            while (m as u64) < threshold {
+                m = u128::from(rng.next_u64()) * u128::from(n);
+            }
+        }
+        (m >> 64) as u64
+    } else {
+        rng.next_u64()
+    }
+}
+
+/// 整数の一様乱数が偏りなく、区間の端を含むことを確認するテスト
+#[test]
+fn test_uniform_integer() {
+    let mut rng = crate::Xorshift160::new(1192_u32);
+    // 3 で割り切れない幅でも、各値がほぼ同じ回数だけ出る
+    let mut counts: [usize; 3] = [0_usize; 3];
+    for _ in 0..30_000 {
+        counts[uniform_integer_0_to(&mut rng, 2_u64) as usize] += 1_usize;
+    }
+    assert!(counts
+        .iter()
+        .all(|c| (9_500_usize..10_500_usize).contains(c)));
+
+    // 32 bit を超える幅と、全区間
+    let large: u64 = (1_u64 << 40) + 7_u64;
+    assert!((0..1_000).all(|_| uniform_integer_0_to(&mut rng, large) <= large));
+    assert!((0..1_000).any(|_| uniform_integer_0_to(&mut rng, u64::MAX) > u64::from(u32::MAX)));
+    assert_eq!(uniform_integer_0_to(&mut rng, 0_u64), 0_u64);
+}
+
+/// 53 bit 精度のエンジンでも標準正規分布のモーメントが理論値と一致し、
+/// 16 bit の格子 W に限られない値が生成されることを確認するテスト
+#[test]
+fn test_standard_normal_high_precision() {
+    let mut rng0 = crate::HighPrecision::new(crate::Xorshift160::new(1192_u32));
+    let mut rng1 = crate::HighPrecision::new(crate::Xorshift160::new(765_u32));
+    let values: Vec<f64> = (0..100_000)
+        .map(|_| standard_normal(&mut rng0, &mut rng1))
+        .collect();
+    let mean: f64 = values.iter().sum::<f64>() / 100_000_f64;
+    let variance: f64 = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 100_000_f64;
+    let within_one: f64 = values.iter().filter(|x| x.abs() < 1_f64).count() as f64 / 100_000_f64;
+    assert!(mean.abs() < 0.02_f64);
+    assert!((variance - 1_f64).abs() < 0.02_f64);
+    assert!((within_one - 0.682_689_f64).abs() < 0.01_f64);
+    assert!(values
+        .iter()
+        .filter(|x| x.abs() < A_NORMAL)
+        .any(|x| ((x.abs() / W_NORMAL) - (x.abs() / W_NORMAL).round()).abs() > 1e-6_f64));
+}
+
+/// エンジンの32 bitの出力を[0, 1]の一様乱数に変換するための分母
 const MAX_U32_AS_F64: f64 = u32::MAX as f64;
 
 // 標準正規分布の定数
-//const A_NORMAL: f64 = 1.17741002252_f64; // √(ln4)
+const A_NORMAL: f64 = 1.17741002252_f64; // √(ln4)
 const B_NORMAL: f64 = 2.50662827463_f64; // √(2π)
 const S_NORMAL: f64 = 0.88579134438_f64; // a / (b - a)
 const K_NORMAL: u32 = 30783_u32; // floor( (2^(m/2) - 1) * a / b )
@@ -118,9 +400,12 @@ const HALF_BIT_NORMAL: u32 = 65535_u32; // 2^(m/2) - 1
 /// 標準正規分布\
 /// アルゴリズム 3.5: Monty Python法に基づいて乱数を計算する。
 #[inline]
-pub(crate) fn standard_normal(xyzuv0: &mut [u32; 5], xyzuv1: &mut [u32; 5]) -> f64 {
+pub(crate) fn standard_normal<R: CoreRng>(rng0: &mut R, rng1: &mut R) -> f64 {
+    if R::HIGH_PRECISION {
+        return standard_normal_high_precision(rng0, rng1);
+    }
     // step 1: m bit符号無整数型の一様乱数を生成する
-    let u_mbit_integer: u32 = xorshift160(xyzuv0);
+    let u_mbit_integer: u32 = rng0.next_u32();
     // step 2:　符号無整数型の一様乱数と 1 のbit論理積を乱数の符号とする。
     let sign: f64 = if (u_mbit_integer & 1_u32) == 1_u32 {
         1_f64
@@ -141,19 +426,50 @@ pub(crate) fn standard_normal(xyzuv0: &mut [u32; 5], xyzuv1: &mut [u32; 5]) -> f
         let u_half_m_minus_1_bit_integer: u32 = u_m_minus_1_bit_integer >> 16_u32;
         // step 7: u_dash = ( u_half_m_minus_1_bit_integer as f64 + 0.5f64) / (2^(m/2) - 2)
         let u_dash: f64 = (f64::from(u_half_m_minus_1_bit_integer) + 0.5_f64) / 65534_f64;
-        // step 8: ln(u_dash) < - u_x^2 / 2 のとき、y = sign * ux を返す
-        if u_dash.ln() * 2_f64 < -u_x.powi(2_i32) {
-            sign * u_x
+        standard_normal_reject(sign, u_x, u_dash, rng0, rng1)
+    }
+}
+
+/// 標準正規分布 (53 bit の一様乱数を使用する場合)\
+/// 32 bit の出力を 16 bit ずつに分割する代わりに、53 bit の一様乱数で Monty Python法を計算する
+#[inline]
+fn standard_normal_high_precision<R: CoreRng>(rng0: &mut R, rng1: &mut R) -> f64 {
+    // step 1-4: 区間 [-1, 1) の一様乱数 v の符号を乱数の符号とし、u_x = |v| * b とする
+    let v: f64 = 2_f64 * uniform_0_or_greater_and_less_than_1(rng0) - 1_f64;
+    let sign: f64 = if v < 0_f64 { -1_f64 } else { 1_f64 };
+    let u_x: f64 = v.abs() * B_NORMAL;
+    // step 5: u_x < a の場合、y = sign * u_x を返す
+    if u_x < A_NORMAL {
+        sign * u_x
+    } else {
+        // step 6-7: 開区間 (0, 1/2) の一様乱数 u_dash を生成する
+        let u_dash: f64 = uniform_greater_than_0_and_less_than_1(rng0) / 2_f64;
+        standard_normal_reject(sign, u_x, u_dash, rng0, rng1)
+    }
+}
+
+/// 標準正規分布\
+/// アルゴリズム 3.5 の step 8 以降 (u_x が a 以上の場合の棄却採択)
+#[inline]
+fn standard_normal_reject<R: CoreRng>(
+    sign: f64,
+    u_x: f64,
+    u_dash: f64,
+    rng0: &mut R,
+    rng1: &mut R,
+) -> f64 {
+    // step 8: ln(u_dash) < - u_x^2 / 2 のとき、y = sign * ux を返す
+    if u_dash.ln() * 2_f64 < -u_x.powi(2_i32) {
+        sign * u_x
+    } else {
+        // step 9: yを計算して、条件を満たす場合、標準正規分布 y を返す
+        let y: f64 = sign * S_NORMAL * (B_NORMAL - u_x);
+        if (P_NORMAL - u_dash).ln() < Q_NORMAL - y.powi(2_i32) / 2_f64 {
+            y
         } else {
-            // step 9: yを計算して、条件を満たす場合、標準正規分布 y を返す
-            let y: f64 = sign * S_NORMAL * (B_NORMAL - u_x);
-            if (P_NORMAL - u_dash).ln() < Q_NORMAL - y.powi(2_i32) / 2_f64 {
-                y
-            } else {
-                // step 10: 条件を満たさない場合、正規分布の裾野を計算する
-                // アルゴリズム 3.1x
-                sign * standard_normal_foot(xyzuv0, xyzuv1)
-            }
+            // step 10: 条件を満たさない場合、正規分布の裾野を計算する
+            // アルゴリズム 3.1x
+            sign * standard_normal_foot(rng0, rng1)
         }
     }
 }
@@ -162,11 +478,11 @@ const D_NORMAL: f64 = core::f64::consts::TAU; // b^2 = 2π
 /// 標準正規分布の裾野\
 /// アルゴリズム 3.13 (棄却採択法) に基づいて乱数を計算する
 #[inline]
-fn standard_normal_foot(xyzuv0: &mut [u32; 5], xyzuv1: &mut [u32; 5]) -> f64 {
+fn standard_normal_foot<R: CoreRng>(rng0: &mut R, rng1: &mut R) -> f64 {
     loop {
         // step 2: 区間[0, 1) の一様乱数 u_1 と [0, 1] の一様乱数 u_2 を生成する
-        let u_1: f64 = xorshift160_0_or_greater_and_less_than_1(xyzuv0);
-        let u_2: f64 = xorshift160_0_to_1(xyzuv1);
+        let u_1: f64 = uniform_0_or_greater_and_less_than_1(rng0);
+        let u_2: f64 = uniform_0_to_1(rng1);
         // 乱数 x を計算する
         let x: f64 = (D_NORMAL - 2_f64 * (1_f64 - u_1).ln()).sqrt();
         // step 3: x * u_2 <= B_NORMAL の場合、Xを標準正規分布の裾野として返す。
@@ -179,9 +495,9 @@ fn standard_normal_foot(xyzuv0: &mut [u32; 5], xyzuv1: &mut [u32; 5]) -> f64 {
 /// 標準コーシー分布\
 /// アルゴリズム 3.26 (逆関数法) に基づいて乱数を計算する
 #[inline]
-pub(crate) fn standard_cauchy(xyzuv: &mut [u32; 5]) -> f64 {
+pub(crate) fn standard_cauchy<R: CoreRng>(rng: &mut R) -> f64 {
     // step 1: 開区間 (0, 1) の一様乱数 u を生成する。
-    let u: f64 = xorshift160_greater_than_0_and_less_than_1(xyzuv);
+    let u: f64 = uniform_greater_than_0_and_less_than_1(rng);
     // step 2: 標準コーシー分布を計算して返す
     (core::f64::consts::PI * (u - 0.5_f64)).tan()
 }
@@ -189,18 +505,18 @@ pub(crate) fn standard_cauchy(xyzuv: &mut [u32; 5]) -> f64 {
 /// 標準指数分布\
 /// アルゴリズム 3.41 (逆関数法) に基づいて乱数を計算する
 #[inline]
-pub(crate) fn standard_exponential(xyzuv: &mut [u32; 5]) -> f64 {
+pub(crate) fn standard_exponential<R: CoreRng>(rng: &mut R) -> f64 {
     // step 1: 区間 [0, 1) の一様乱数を生成する
     // step 2: y = -ln(1 - u) を標準指数分布として返す
-    -(1_f64 - xorshift160_0_or_greater_and_less_than_1(xyzuv)).ln()
+    -(1_f64 - uniform_0_or_greater_and_less_than_1(rng)).ln()
 }
 
 /// 標準ラプラス分布\
 /// アルゴリズム 3.45 (逆関数法) に基づいて乱数を計算する
 #[inline]
-pub(crate) fn standard_laplace(xyzuv: &mut [u32; 5]) -> f64 {
+pub(crate) fn standard_laplace<R: CoreRng>(rng: &mut R) -> f64 {
     // step 1: 開区間 (0, 1) の一様乱数を生成する
-    let u: f64 = xorshift160_greater_than_0_and_less_than_1(xyzuv);
+    let u: f64 = uniform_greater_than_0_and_less_than_1(rng);
     // step 2: u が 0.5 より小さいか否かで分岐して、標準ラプラス分布を計算して返す
     if u < 0.5_f64 {
         (2_f64 * u).ln()
@@ -209,28 +525,49 @@ pub(crate) fn standard_laplace(xyzuv: &mut [u32; 5]) -> f64 {
     }
 }
 
+/// 標準ロジスティック分布\
+/// 逆関数法: 開区間 (0, 1) の一様乱数 u から ln(u / (1 - u)) を計算する
+#[inline]
+pub(crate) fn standard_logistic<R: CoreRng>(rng: &mut R) -> f64 {
+    let u: f64 = uniform_greater_than_0_and_less_than_1(rng);
+    (u / (1_f64 - u)).ln()
+}
+
+/// 指数べき分布の形状母数の下限\
+/// p が小さいと |x| ≒ (1/p)^(1/p) が f64 の範囲を超えるため (p = 0.007 で 10^308 程度)
+pub(crate) const MIN_SHAPE_EXPONENTIAL_POWER: f64 = 0.01_f64;
+
+/// 標準指数べき分布の絶対値 (半指数べき分布)\
+/// 形状母数 1/p のガンマ分布に従う乱数 g から、 g^(1/p) を計算する\
+/// p が大きいと g は 0 にアンダーフローし得るため、対数 ln(g) / p から計算する
+#[inline]
+pub(crate) fn standard_half_exponential_power<R: CoreRng>(
+    rng_uniform: &mut R,
+    rng_normal_0: &mut R,
+    rng_normal_1: &mut R,
+    shape: &f64,
+) -> f64 {
+    let shape_inverse: f64 = 1_f64 / *shape;
+    (standard_gamma_ln(rng_uniform, rng_normal_0, rng_normal_1, &shape_inverse) / *shape).exp()
+}
+
 /// 標準ガンマ分布(β = 1 の場合のガンマ分布)\
 /// アルゴリズム 3.60 に基づいて乱数を計算する
 #[inline]
-pub(crate) fn standard_gamma(
-    xyzuv_uniform: &mut [u32; 5],
-    xyzuv_normal_0: &mut [u32; 5],
-    xyzuv_normal_1: &mut [u32; 5],
+pub(crate) fn standard_gamma<R: CoreRng>(
+    rng_uniform: &mut R,
+    rng_normal_0: &mut R,
+    rng_normal_1: &mut R,
     alpha: &f64,
 ) -> f64 {
     // α = 1 のときは標準指数分布を返す
     if *alpha == 1_f64 {
-        return standard_exponential(xyzuv_uniform);
+        return standard_exponential(rng_uniform);
     }
     // α < 1 のときは再帰的に乱数を計算する
     else if *alpha < 1_f64 {
-        let y: f64 = standard_gamma(
-            xyzuv_uniform,
-            xyzuv_normal_0,
-            xyzuv_normal_1,
-            &(alpha + 1_f64),
-        );
-        let t: f64 = xorshift160_greater_than_0_and_less_than_1(xyzuv_uniform);
+        let y: f64 = standard_gamma(rng_uniform, rng_normal_0, rng_normal_1, &(alpha + 1_f64));
+        let t: f64 = uniform_greater_than_0_and_less_than_1(rng_uniform);
         let alpha_inverse: f64 = 1_f64 / *alpha;
         return y * t.powf(alpha_inverse);
     }
@@ -239,7 +576,7 @@ pub(crate) fn standard_gamma(
     let c: f64 = (9_f64 * d).powf(-0.5);
     loop {
         // step 1: 標準正規分布に従う乱数 z を生成して、 v = (1 + cz) を計算する
-        let z: f64 = standard_normal(xyzuv_normal_0, xyzuv_normal_1);
+        let z: f64 = standard_normal(rng_normal_0, rng_normal_1);
         let v: f64 = 1_f64 + c * z;
         // step 2: v > 0 になるまで step 1 を繰り返す。
         //         その後、 w = v^3, y = dw を計算する。
@@ -247,7 +584,7 @@ pub(crate) fn standard_gamma(
             let w: f64 = v.powi(3);
             let y: f64 = d * w;
             // step 3: 開区間 (0, 1) の一様乱数 u を生成して, u ≦ 1 0.0331z^4 の場合, step 5 に進
-            let u: f64 = xorshift160_greater_than_0_and_less_than_1(xyzuv_uniform);
+            let u: f64 = uniform_greater_than_0_and_less_than_1(rng_uniform);
             if u <= 1_f64 - 0.0331 * z.powi(4) {
                 // step 5: y を β = 1 の場合のガンマ分布として返す
                 return y;
@@ -260,3 +597,22 @@ pub(crate) fn standard_gamma(
         }
     }
 }
+
+/// 標準ガンマ分布に従う乱数の対数\
+/// α < 1 のときは ln(y) + ln(t) / α として計算するため、α が非常に小さくても 0 にアンダーフローしない
+#[inline]
+pub(crate) fn standard_gamma_ln<R: CoreRng>(
+    rng_uniform: &mut R,
+    rng_normal_0: &mut R,
+    rng_normal_1: &mut R,
+    alpha: &f64,
+) -> f64 {
+    if *alpha < 1_f64 {
+        // standard_gamma と同じ順序で乱数を消費する
+        let y: f64 = standard_gamma(rng_uniform, rng_normal_0, rng_normal_1, &(alpha + 1_f64));
+        let t: f64 = uniform_greater_than_0_and_less_than_1(rng_uniform);
+        y.ln() + t.ln() / *alpha
+    } else {
+        standard_gamma(rng_uniform, rng_normal_0, rng_normal_1, alpha).ln()
+    }
+}
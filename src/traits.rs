@@ -0,0 +1,379 @@
+/// Core pseudo-random number generator (engine) that the probability distributions draw from.
+///
+/// Every distribution is generic over its engine `R`, with [`crate::Xorshift160`] as the default,
+/// so the whole distribution catalogue can run on any engine implementing this trait.
+/// Only `next_u32` and `from_seed_sequence` are required; `next_u64` and `fill_bytes` are
+/// derived from `next_u32` unless an engine has a faster native path.
+///
+/// The uniform random numbers behind every distribution are made from one `next_u32` output,
+/// i.e. 2^32 distinct values. Engines with [`Self::HIGH_PRECISION`] set to `true`, such as
+/// [`crate::HighPrecision`] and [`crate::Mt19937`], draw a 53 bit mantissa with [`Self::next_f64_53`] instead.
+///
+/// # Example
+/// ```
+/// use rand_simple::{CoreRng, Normal, SeedSequence, Xorshift160};
+///
+/// // Run a distribution on an explicitly constructed engine
+/// let mut seeds = SeedSequence::from_u64(1192_u64);
+/// let rngs = [Xorshift160::from_seed_sequence(&mut seeds), Xorshift160::from_seed_sequence(&mut seeds)];
+/// let mut normal = Normal::from_rngs(rngs);
+/// println!("Returns a random number -> {}", normal.sample());
+///
+/// // Or let the distribution seed every stream of the chosen engine
+/// let mut normal = Normal::<Xorshift160>::from_rng_seed(1192_u64);
+/// println!("Returns a random number -> {}", normal.sample());
+/// ```
+pub trait CoreRng {
+    /// Whether the distributions convert two 32 bit outputs into a uniform random number
+    /// with the full 53 bit precision of `f64`, instead of one output with 32 bits.
+    /// Defaults to `false`, which keeps the sequences of earlier versions.
+    const HIGH_PRECISION: bool = false;
+
+    /// Return the next 32 bit output.
+    fn next_u32(&mut self) -> u32;
+
+    /// Return the next 64 bit output.
+    /// By default, two outputs of `next_u32` are combined, the first one as the low 32 bits.
+    fn next_u64(&mut self) -> u64 {
+        let low: u64 = u64::from(self.next_u32());
+        let high: u64 = u64::from(self.next_u32());
+        (high << 32) | low
+    }
+
+    /// Return a uniform random number in [0, 1) with 53 bit resolution,
+    /// which the distributions use when [`Self::HIGH_PRECISION`] is `true`.
+    /// By default, the upper 27 and 26 bits of two `next_u32` outputs are combined as `genrand_res53` does.
+    /// The 64 bit engines override it with the upper 53 bits of one `next_u64` output.
+    fn next_f64_53(&mut self) -> f64 {
+        let a: u32 = self.next_u32() >> 5;
+        let b: u32 = self.next_u32() >> 6;
+        (f64::from(a) * 67_108_864_f64 + f64::from(b)) / 9_007_199_254_740_992_f64
+    }
+
+    /// Fill `dest` with random bytes.
+    /// By default, each output of `next_u32` is written in little-endian order.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4_usize) {
+            let bytes = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    /// Create an engine whose whole state is derived from a [`crate::SeedSequence`].
+    fn from_seed_sequence(seeds: &mut crate::SeedSequence) -> Self
+    where
+        Self: Sized;
+}
+
+/// Engine that can hand out substreams to parallel workers.
+///
+/// Used by the `split` and `fork` methods of [`SplittableDistribution`].
+pub trait SplittableRng: CoreRng + Clone {
+    /// Jump ahead past the substream that a child split off at the current position may use.
+    ///
+    /// How far apart the substreams are depends on the engine:
+    /// * [`crate::Xorshift160`], [`crate::Philox4x32`] and [`crate::Pcg64`] advance by 2^100 words,
+    ///   [`crate::Xoshiro256StarStar`] and [`crate::Xoshiro256Plus`] by 2^128 steps, and
+    ///   [`crate::Pcg32`] by 2^48 steps, so substreams do not overlap while each draws fewer values than that.
+    /// * [`crate::ChaCha20`] moves to another stream number, whose key stream is independent
+    ///   as long as the stream numbers differ.
+    fn jump_substream(&mut self);
+
+    /// Split off a child engine that continues from the current position,
+    /// and move `self` to the next substream.
+    fn split(&mut self) -> Self {
+        let child = self.clone();
+        self.jump_substream();
+        child
+    }
+}
+
+/// Distribution whose engines can be split into substreams for parallel workers.
+///
+/// Implemented by every distribution running on a [`SplittableRng`]; each internal engine jumps
+/// with [`SplittableRng::jump_substream`], so the guarantee on overlap is that of the engine.
+///
+/// # Example
+/// ```
+/// use rand_simple::SplittableDistribution;
+///
+/// let mut normal = rand_simple::Normal::new([1192_u32, 765_u32]);
+/// let mut child = normal.split();
+/// println!("Returns a random number -> {}", child.sample());
+/// assert_eq!(normal.fork(4_usize).len(), 4_usize);
+/// ```
+pub trait SplittableDistribution: Clone {
+    /// Move every internal engine to its next substream.
+    fn jump_substreams(&mut self);
+
+    /// Split off a child generator for a parallel worker.
+    /// The child continues from the current state, and `self` moves to the next substream.
+    fn split(&mut self) -> Self {
+        let child = self.clone();
+        self.jump_substreams();
+        child
+    }
+
+    /// Split off `n` child generators by calling [`Self::split`] repeatedly.
+    fn fork(&mut self, n: usize) -> Vec<Self>
+    where
+        Self: Sized,
+    {
+        (0..n).map(|_| self.split()).collect()
+    }
+}
+
+/// Engine whose output at any position can be evaluated directly, such as a counter-based engine.
+///
+/// Used by the `sample_at` methods of the inverse-transform distributions.
+pub trait RandomAccessRng: CoreRng + Clone {
+    /// Move to `position`, the number of 32 bit words from the start of the stream,
+    /// without generating the preceding values.
+    fn seek(&mut self, position: u128);
+
+    /// Returns the position of the next 32 bit word.
+    fn position(&self) -> u128;
+}
+
+/// Common interface implemented by every probability distribution in this crate.
+///
+/// Each struct keeps its inherent `sample` method; this trait exposes the same
+/// sampling path so that simulation code can be written generically over
+/// "some distribution", or store heterogeneous generators as trait objects.
+///
+/// The associated type `Output` is `f64` for continuous distributions,
+/// `u64` for discrete ones such as `Bernoulli` and `Geometric`, the integer type `T` for
+/// `UniformInt<T>`, and `Vec<f64>` for the random vectors of `Dirichlet`.
+///
+/// # Example
+/// ```
+/// use rand_simple::Distribution;
+///
+/// // Generic Monte Carlo driver
+/// fn mean<D: Distribution<Output = f64>>(distribution: &mut D, n: usize) -> f64 {
+///     (0..n).map(|_| distribution.sample()).sum::<f64>() / n as f64
+/// }
+/// let mut uniform = rand_simple::Uniform::new(1192_u32);
+/// assert!((mean(&mut uniform, 10_000) - 0.5_f64).abs() < 0.05_f64);
+///
+/// // Heterogeneous collection of generators
+/// let mut generators: Vec<Box<dyn Distribution<Output = f64>>> = vec![
+///     Box::new(rand_simple::Normal::new([1192_u32, 765_u32])),
+///     Box::new(rand_simple::Exponential::new(1543_u32)),
+/// ];
+/// for generator in generators.iter_mut() {
+///     println!("Returns a random number -> {}", generator.sample());
+/// }
+/// ```
+pub trait Distribution {
+    /// Type of the generated random numbers.
+    type Output;
+
+    /// Generate a random number.
+    fn sample(&mut self) -> Self::Output;
+
+    /// Generate `n` random numbers and collect them into a `Vec`.
+    /// # Example
+    /// ```
+    /// use rand_simple::Distribution;
+    /// let mut exponential = rand_simple::Exponential::new(1192_u32);
+    /// let samples: Vec<f64> = exponential.sample_n(1_000);
+    /// assert_eq!(samples.len(), 1_000);
+    /// ```
+    fn sample_n(&mut self, n: usize) -> Vec<Self::Output> {
+        (0..n).map(|_| self.sample()).collect()
+    }
+
+    /// Overwrite every element of a pre-allocated buffer with random numbers.
+    /// # Example
+    /// ```
+    /// use rand_simple::Distribution;
+    /// let mut bernoulli = rand_simple::Bernoulli::new(1192_u32);
+    /// let mut buffer = [0_u64; 64];
+    /// bernoulli.fill(&mut buffer);
+    /// assert!(buffer.iter().all(|x| *x <= 1_u64));
+    /// ```
+    fn fill(&mut self, buffer: &mut [Self::Output]) {
+        for x in buffer.iter_mut() {
+            *x = self.sample();
+        }
+    }
+
+    /// Generate a random number as `f32`, rounded to the nearest representable value.
+    /// Available for the continuous distributions, whose `Output` is `f64`.
+    /// # Example
+    /// ```
+    /// use rand_simple::Distribution;
+    /// let mut normal = rand_simple::Normal::new([1192_u32, 765_u32]);
+    /// let x: f32 = normal.sample_f32();
+    /// println!("Returns a random number -> {x}");
+    /// ```
+    fn sample_f32(&mut self) -> f32
+    where
+        Self::Output: Into<f64>,
+    {
+        self.sample().into() as f32
+    }
+
+    /// Overwrite every element of a pre-allocated `f32` buffer with random numbers (see [`Self::sample_f32`]).
+    /// # Example
+    /// ```
+    /// use rand_simple::Distribution;
+    /// let mut uniform = rand_simple::Uniform::new(1192_u32);
+    /// let mut buffer = [0_f32; 64];
+    /// uniform.fill_f32(&mut buffer);
+    /// assert!(buffer.iter().all(|x| (0_f32..=1_f32).contains(x)));
+    /// ```
+    fn fill_f32(&mut self, buffer: &mut [f32])
+    where
+        Self::Output: Into<f64>,
+    {
+        for x in buffer.iter_mut() {
+            *x = self.sample_f32();
+        }
+    }
+
+    /// Return an infinite iterator of random numbers borrowing the generator.
+    /// # Example
+    /// ```
+    /// use rand_simple::Distribution;
+    /// let mut normal = rand_simple::Normal::new([1192_u32, 765_u32]);
+    /// let positives: usize = normal.sample_iter().take(1_000).filter(|x| *x > 0_f64).count();
+    /// println!("Number of positive values -> {positives}");
+    /// // The generator can be used again after the iterator is dropped
+    /// println!("Returns a random number -> {}", normal.sample());
+    /// ```
+    fn sample_iter(&mut self) -> SampleIter<'_, Self>
+    where
+        Self: Sized,
+    {
+        SampleIter { distribution: self }
+    }
+}
+
+// トレイトオブジェクトや可変参照を経由しても一括生成を利用できるようにする
+impl<D: Distribution + ?Sized> Distribution for Box<D> {
+    type Output = D::Output;
+
+    fn sample(&mut self) -> D::Output {
+        (**self).sample()
+    }
+}
+
+impl<D: Distribution + ?Sized> Distribution for &mut D {
+    type Output = D::Output;
+
+    fn sample(&mut self) -> D::Output {
+        (**self).sample()
+    }
+}
+
+/// Infinite iterator over random numbers, returned by [`Distribution::sample_iter`].
+pub struct SampleIter<'a, D: Distribution> {
+    distribution: &'a mut D, // 乱数を生成する確率分布
+}
+
+impl<D: Distribution> Iterator for SampleIter<'_, D> {
+    type Item = D::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.distribution.sample())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// トレイト経由の乱数が固有メソッドと同じ系列になることを確認するテスト
+#[test]
+fn test_distribution_trait() {
+    let mut inherent = crate::Normal::new([1192_u32, 765_u32]);
+    let mut generic: Box<dyn Distribution<Output = f64>> =
+        Box::new(crate::Normal::new([1192_u32, 765_u32]));
+    for _ in 0..100 {
+        assert_eq!(inherent.sample(), generic.sample());
+    }
+
+    let mut inherent = crate::Geometric::new(1192_u32);
+    let mut generic: Box<dyn Distribution<Output = u64>> =
+        Box::new(crate::Geometric::new(1192_u32));
+    for _ in 0..100 {
+        assert_eq!(inherent.sample(), generic.sample());
+    }
+}
+
+/// 一括生成のメソッドが sample の繰り返しと同じ系列になることを確認するテスト
+#[test]
+fn test_bulk_sampling() {
+    let mut reference = crate::Gamma::new([1192_u32, 765_u32, 1543_u32]);
+    let expected: Vec<f64> = (0..30).map(|_| reference.sample()).collect();
+
+    let mut gamma = crate::Gamma::new([1192_u32, 765_u32, 1543_u32]);
+    let mut actual: Vec<f64> = gamma.sample_n(10);
+    let mut buffer = [0_f64; 10];
+    gamma.fill(&mut buffer);
+    actual.extend_from_slice(&buffer);
+    actual.extend(gamma.sample_iter().take(10));
+    assert_eq!(actual, expected);
+
+    // トレイトオブジェクトからも呼び出せる
+    let mut boxed: Box<dyn Distribution<Output = u64>> = Box::new(crate::Bernoulli::new(1192_u32));
+    assert_eq!(boxed.sample_iter().take(5).count(), 5);
+
+    // f32 の乱数は f64 の乱数を丸めた値になる
+    let mut reference = crate::Gamma::new([1192_u32, 765_u32, 1543_u32]);
+    let mut gamma = crate::Gamma::new([1192_u32, 765_u32, 1543_u32]);
+    let mut buffer = [0_f32; 10];
+    gamma.fill_f32(&mut buffer);
+    assert_eq!(buffer, [(); 10].map(|_| reference.sample() as f32));
+    assert_eq!(gamma.sample_f32(), reference.sample() as f32);
+}
+
+/// 既定の実装の next_u64 と fill_bytes が next_u32 の系列と一致することを確認するテスト
+#[test]
+fn test_core_rng_defaults() {
+    let mut reference = crate::Xorshift160::new(1192_u32);
+    let first: u32 = reference.next_u32();
+    let second: u32 = reference.next_u32();
+    let third: u32 = reference.next_u32();
+
+    let mut rng = crate::Xorshift160::new(1192_u32);
+    assert_eq!(rng.next_u64(), (u64::from(second) << 32) | u64::from(first));
+    let mut bytes = [0_u8; 3];
+    rng.fill_bytes(&mut bytes);
+    assert_eq!(bytes, third.to_le_bytes()[..3]);
+}
+
+/// 独自のエンジンで各確率分布を利用できることを確認するテスト
+#[test]
+fn test_custom_engine() {
+    // 線形合同法による最小限のエンジン
+    #[derive(Debug, Clone, PartialEq)]
+    struct Lcg(u64);
+    impl CoreRng for Lcg {
+        fn next_u32(&mut self) -> u32 {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005_u64)
+                .wrapping_add(1_442_695_040_888_963_407_u64);
+            (self.0 >> 32) as u32
+        }
+        fn from_seed_sequence(seeds: &mut crate::SeedSequence) -> Self {
+            Self(seeds.next_u64())
+        }
+    }
+
+    let mut gamma = crate::Gamma::<Lcg>::from_rng_seed(1192_u64);
+    gamma.try_set_params(2_f64, 1.5_f64).unwrap();
+    let mean: f64 = gamma.sample_n(10_000).iter().sum::<f64>() / 10_000_f64;
+    assert!((mean - 3_f64).abs() < 0.1_f64);
+
+    let mut generators: Vec<Box<dyn Distribution<Output = f64>>> = vec![
+        Box::new(crate::Uniform::from_rng(Lcg(1192_u64))),
+        Box::new(crate::Normal::from_rngs([Lcg(1192_u64), Lcg(765_u64)])),
+    ];
+    for generator in generators.iter_mut() {
+        assert!(generator.sample().is_finite());
+    }
+}